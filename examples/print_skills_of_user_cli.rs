@@ -15,7 +15,7 @@ async fn main() {
     println!("Skills of user:");
     // Get user and print skills
     client
-        .get_user(&username.trim())
+        .get_user(username.trim())
         .await
         .unwrap()
         .skills
@@ -0,0 +1,4 @@
+//! Analytics over completion history of users
+
+pub mod activity;
+pub mod completions;
@@ -0,0 +1,201 @@
+//! Activity calendar and streaks computed from completion times
+//!
+//! All values are computed in a time zone supplied by caller, because day of completion depends on it.
+
+use crate::rest_api::models::CompletedChallenge;
+use chrono::{DateTime, Datelike, Days, NaiveDate, TimeZone, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Represents activity of a user
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct Activity {
+    /// Number of completions on each day with at least one completion, e.g. for a heatmap
+    pub per_day: BTreeMap<NaiveDate, u64>,
+    /// Number of completions on each weekday, starting from Monday
+    pub per_weekday: [u64; 7],
+    /// Number of completions in each hour of a day
+    pub per_hour: [u64; 24],
+    /// Number of consecutive days with completions, ending today or yesterday
+    pub current_streak: u64,
+    /// Largest number of consecutive days with completions
+    pub longest_streak: u64,
+    /// Weekday with the most completions, if there are any
+    pub most_active_weekday: Option<Weekday>,
+    /// Hour of a day with the most completions, if there are any
+    pub most_active_hour: Option<u32>,
+}
+
+/// Implementation of Activity
+impl Activity {
+    /// Compute activity from completion times
+    ///
+    /// Streak is current if it ends today or yesterday, because today isn't over yet
+    ///
+    /// # Arguments:
+    /// * completed (&[CompletedChallenge]) - completed katas
+    /// * timezone (&Tz) - time zone to compute days and hours in
+    /// * today (NaiveDate) - current date in the time zone
+    ///
+    /// # Errors:
+    /// * `invalid completion time: {completed_at}` - If completion time is not in RFC 3339 format
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{FixedOffset, NaiveDate};
+    /// use codewars_api::analytics::activity::Activity;
+    /// use codewars_api::rest_api::models::CompletedChallenge;
+    ///
+    /// let completed: Vec<CompletedChallenge> = ["2024-03-01T10:00:00Z", "2024-03-01T23:30:00Z"]
+    ///     .iter()
+    ///     .map(|time| CompletedChallenge {
+    ///         completed_at: time.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .collect();
+    /// // Second completion is on the next day in UTC+3
+    /// let timezone = FixedOffset::east_opt(3 * 3600).unwrap();
+    /// let today = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+    /// let activity = Activity::new(&completed, &timezone, today).unwrap();
+    /// assert_eq!(activity.current_streak, 2);
+    /// ```
+    pub fn new<Tz: TimeZone>(
+        completed: &[CompletedChallenge],
+        timezone: &Tz,
+        today: NaiveDate,
+    ) -> Result<Self, String> {
+        let mut activity = Self::default();
+        for completion in completed {
+            let time = DateTime::parse_from_rfc3339(&completion.completed_at)
+                .map_err(|_| format!("invalid completion time: {}", completion.completed_at))?
+                .with_timezone(timezone);
+            *activity.per_day.entry(time.date_naive()).or_default() += 1;
+            activity.per_weekday[time.weekday().num_days_from_monday() as usize] += 1;
+            activity.per_hour[time.hour() as usize] += 1;
+        }
+        activity.most_active_weekday =
+            most_active(&activity.per_weekday).map(|index| Weekday::try_from(index as u8).unwrap());
+        activity.most_active_hour = most_active(&activity.per_hour).map(|index| index as u32);
+        activity.longest_streak = longest_streak(activity.per_day.keys());
+        activity.current_streak = current_streak(&activity.per_day, today);
+        Ok(activity)
+    }
+
+    /// Compute activity from completion times, using current date in the time zone as today
+    ///
+    /// # Errors:
+    /// See [`Activity::new`]
+    pub fn until_now<Tz: TimeZone>(
+        completed: &[CompletedChallenge],
+        timezone: &Tz,
+    ) -> Result<Self, String> {
+        let today = Utc::now().with_timezone(timezone).date_naive();
+        Self::new(completed, timezone, today)
+    }
+}
+
+// Get index of the largest count, preferring the first one on ties
+fn most_active(counts: &[u64]) -> Option<usize> {
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+        .map(|(index, _)| index)
+}
+
+// Get length of the longest run of consecutive days. Days must be sorted
+fn longest_streak<'a>(days: impl Iterator<Item = &'a NaiveDate>) -> u64 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        current = match previous {
+            Some(previous) if previous.checked_add_days(Days::new(1)) == Some(*day) => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(*day);
+    }
+    longest
+}
+
+// Get length of the run of consecutive days ending today or yesterday
+fn current_streak(per_day: &BTreeMap<NaiveDate, u64>, today: NaiveDate) -> u64 {
+    let mut day = match per_day.contains_key(&today) {
+        true => today,
+        false => match today.pred_opt() {
+            Some(yesterday) => yesterday,
+            None => return 0,
+        },
+    };
+    let mut streak = 0;
+    while per_day.contains_key(&day) {
+        streak += 1;
+        match day.pred_opt() {
+            Some(previous) => day = previous,
+            None => break,
+        }
+    }
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for activity analytics
+
+    use super::*;
+    use crate::fixtures::completed_at as completed;
+    use chrono::FixedOffset;
+
+    // Build date
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Test streaks and most active weekday and hour
+    #[test]
+    fn test_activity() {
+        let completed = completed(&[
+            "2024-02-27T09:00:00Z",
+            "2024-02-28T09:10:00Z",
+            "2024-02-29T09:20:00Z",
+            "2024-03-01T18:00:00Z",
+            "2024-03-05T09:00:00Z",
+            "2024-03-06T20:00:00Z",
+        ]);
+        let activity = Activity::new(&completed, &Utc, date(2024, 3, 7)).unwrap();
+        assert_eq!(activity.longest_streak, 4);
+        assert_eq!(activity.current_streak, 2);
+        assert_eq!(activity.per_day[&date(2024, 2, 29)], 1);
+        // Two completions on Tuesday and two on Wednesday, Tuesday is the first
+        assert_eq!(activity.most_active_weekday, Some(Weekday::Tue));
+        assert_eq!(activity.most_active_hour, Some(9));
+        // Streak is broken if there was no completion yesterday
+        let activity = Activity::new(&completed, &Utc, date(2024, 3, 8)).unwrap();
+        assert_eq!(activity.current_streak, 0);
+    }
+
+    /// Test that days are computed in the time zone
+    #[test]
+    fn test_timezone() {
+        let completed = completed(&["2024-03-01T22:00:00Z", "2024-03-02T01:00:00Z"]);
+        let utc = Activity::new(&completed, &Utc, date(2024, 3, 2)).unwrap();
+        assert_eq!(utc.per_day.len(), 2);
+        let timezone = FixedOffset::west_opt(5 * 3600).unwrap();
+        let local = Activity::new(&completed, &timezone, date(2024, 3, 1)).unwrap();
+        assert_eq!(local.per_day, BTreeMap::from([(date(2024, 3, 1), 2)]));
+        assert_eq!(local.per_hour[17], 1);
+        assert_eq!(local.per_hour[20], 1);
+        assert_eq!(local.most_active_weekday, Some(Weekday::Fri));
+    }
+
+    /// Test that invalid completion time is reported
+    #[test]
+    fn test_invalid_time() {
+        assert_eq!(
+            Activity::new(&completed(&["yesterday"]), &Utc, date(2024, 3, 1)),
+            Err("invalid completion time: yesterday".to_string())
+        );
+    }
+}
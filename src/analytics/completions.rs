@@ -0,0 +1,184 @@
+//! Breakdowns of completed katas by language, rank, category and tag
+//!
+//! Completed katas don't contain rank, category and tags, so they are joined with
+//! [`CodeChallenge`] metadata, e.g. fetched with [`fetch_katas`].
+
+use crate::rest_api::api::get_kata_if_exists;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{CodeChallenge, CompletedChallenge};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Represents breakdowns of completed katas
+///
+/// Ranks are keyed by their integer value, [-8, -1] maps to kyu, [1, 8] maps to dan
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone)]
+pub struct CompletionStats {
+    /// Total number of completed katas
+    pub total: u64,
+    /// Number of completed katas without metadata
+    pub missing_metadata: u64,
+    /// Number of katas completed in each language
+    pub per_language: BTreeMap<String, u64>,
+    /// Number of completed katas of each rank
+    pub per_rank: BTreeMap<i8, u64>,
+    /// Number of completed katas without rank, e.g. in beta
+    pub unranked: u64,
+    /// Number of completed katas in each category
+    pub per_category: BTreeMap<String, u64>,
+    /// Number of completed katas with each tag
+    pub per_tag: BTreeMap<String, u64>,
+    /// Number of katas of each rank completed in each language
+    pub per_language_rank: BTreeMap<String, BTreeMap<i8, u64>>,
+    /// Percentage of each rank among ranked katas completed in each language
+    pub rank_percentage_per_language: BTreeMap<String, BTreeMap<i8, f64>>,
+}
+
+/// Implementation of CompletionStats
+impl CompletionStats {
+    /// Compute breakdowns of completed katas
+    ///
+    /// # Arguments:
+    /// * completed (&[CompletedChallenge]) - completed katas, e.g. from `get_all_completed_challenges`
+    /// * katas (&HashMap<String, CodeChallenge>) - metadata of katas keyed by ID
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::analytics::completions::{fetch_katas, CompletionStats};
+    /// use codewars_api::RestCodewarsClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = RestCodewarsClient::new();
+    /// let completed = client.get_all_completed_challenges("ANKDDEV").await.unwrap();
+    /// let katas = fetch_katas(&client, &completed).await.unwrap();
+    /// let stats = CompletionStats::new(&completed, &katas);
+    /// // Print number of katas completed in each language
+    /// for (language, count) in &stats.per_language {
+    ///     println!("{}: {}", language, count);
+    /// }
+    /// # }
+    /// ```
+    pub fn new(completed: &[CompletedChallenge], katas: &HashMap<String, CodeChallenge>) -> Self {
+        let mut stats = Self {
+            total: completed.len() as u64,
+            ..Self::default()
+        };
+        for completion in completed {
+            let languages = completion.languages();
+            for language in &languages {
+                *stats.per_language.entry(language.clone()).or_default() += 1;
+            }
+            let Some(kata) = katas.get(&completion.id) else {
+                stats.missing_metadata += 1;
+                continue;
+            };
+            *stats.per_category.entry(kata.category.clone()).or_default() += 1;
+            for tag in &kata.tags {
+                *stats.per_tag.entry(tag.clone()).or_default() += 1;
+            }
+            match &kata.rank {
+                Some(rank) => {
+                    *stats.per_rank.entry(rank.id).or_default() += 1;
+                    for language in &languages {
+                        *stats
+                            .per_language_rank
+                            .entry(language.to_string())
+                            .or_default()
+                            .entry(rank.id)
+                            .or_default() += 1;
+                    }
+                }
+                None => stats.unranked += 1,
+            }
+        }
+        stats.rank_percentage_per_language = stats
+            .per_language_rank
+            .iter()
+            .map(|(language, ranks)| {
+                let total: u64 = ranks.values().sum();
+                let percentages = ranks
+                    .iter()
+                    .map(|(rank, count)| (*rank, *count as f64 * 100.0 / total as f64))
+                    .collect();
+                (language.clone(), percentages)
+            })
+            .collect();
+        stats
+    }
+}
+
+/// Fetch metadata of completed katas with `get_kata`
+///
+/// Katas which were deleted from Codewars are skipped
+///
+/// # Returns:
+/// * Result<HashMap<String, CodeChallenge>, String> - Result that contains katas keyed by ID or an error message
+pub async fn fetch_katas(
+    client: &dyn CodewarsApi,
+    completed: &[CompletedChallenge],
+) -> Result<HashMap<String, CodeChallenge>, String> {
+    let mut katas = HashMap::new();
+    for completion in completed {
+        if katas.contains_key(&completion.id) {
+            continue;
+        }
+        if let Some(kata) = get_kata_if_exists(client, &completion.id).await? {
+            katas.insert(completion.id.clone(), kata);
+        }
+    }
+    Ok(katas)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for completion statistics
+
+    use super::*;
+    use crate::fixtures;
+
+    // Build completed kata and its metadata
+    fn kata(
+        id: &str,
+        languages: &[&str],
+        rank: Option<i8>,
+        tags: &[&str],
+    ) -> (CompletedChallenge, CodeChallenge) {
+        let mut completed = fixtures::completed_ids(&[id]).remove(0);
+        completed.completed_languages = languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect();
+        (completed, fixtures::kata_with(id, rank, languages, tags))
+    }
+
+    /// Test breakdowns of completed katas
+    #[test]
+    fn test_completion_stats() {
+        let (completed, katas): (Vec<_>, Vec<_>) = [
+            kata("a", &["python", "rust", "python"], Some(-8), &["Strings"]),
+            kata("b", &["python"], Some(-8), &["Strings", "Algorithms"]),
+            kata("c", &["python"], Some(-4), &["Algorithms"]),
+            kata("d", &["rust"], None, &[]),
+        ]
+        .into_iter()
+        .unzip();
+        let mut katas: HashMap<String, CodeChallenge> = katas
+            .into_iter()
+            .map(|kata| (kata.id.clone(), kata))
+            .collect();
+        katas.remove("d");
+        let stats = CompletionStats::new(&completed, &katas);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.missing_metadata, 1);
+        assert_eq!(stats.per_language["python"], 3);
+        assert_eq!(stats.per_language["rust"], 2);
+        assert_eq!(stats.per_rank, BTreeMap::from([(-8, 2), (-4, 1)]));
+        assert_eq!(stats.per_category["algorithms"], 3);
+        assert_eq!(stats.per_tag["Strings"], 2);
+        assert_eq!(stats.per_language_rank["rust"], BTreeMap::from([(-8, 1)]));
+        let python = &stats.rank_percentage_per_language["python"];
+        assert!((python[&-8] - 200.0 / 3.0).abs() < 1e-9);
+        assert!((python[&-4] - 100.0 / 3.0).abs() < 1e-9);
+    }
+}
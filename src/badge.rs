@@ -0,0 +1,235 @@
+//! SVG badges and profile cards of users
+//!
+//! Badges look like shields.io badges: `codewars | 3 kyu | 544 honor`. Profile cards also list
+//! ranks in every language. Ranks are filled with colors used by Codewars for [`Color`].
+
+use crate::rest_api::models::{Color, Rank, User};
+
+/// Color of the Codewars label
+const LABEL_COLOR: &str = "#b1361e";
+/// Color of the honor segment and card background
+const DARK_COLOR: &str = "#303133";
+/// Approximate width of a character of 11px Verdana
+const CHAR_WIDTH: usize = 7;
+/// Horizontal padding of a badge segment
+const PADDING: usize = 10;
+/// Width of a profile card
+const CARD_WIDTH: usize = 320;
+/// Height of a language row of a profile card
+const ROW_HEIGHT: usize = 26;
+/// Font of badges and cards
+const FONT: &str = "Verdana,Geneva,DejaVu Sans,sans-serif";
+
+/// Get hex color of the rank color as used by Codewars
+///
+/// # Examples
+/// ```
+/// use codewars_api::badge::rank_color;
+/// use codewars_api::rest_api::models::Color;
+///
+/// assert_eq!(rank_color(&Color::Blue), "#3c7ebb");
+/// ```
+pub fn rank_color(color: &Color) -> &'static str {
+    match color {
+        Color::White => "#e6e6e6",
+        Color::Yellow => "#ecb613",
+        Color::Blue => "#3c7ebb",
+        Color::Purple => "#866cc7",
+        Color::Black => "#555555",
+        Color::Red => "#bb432c",
+    }
+}
+
+/// Render badge with overall rank and honor of the user
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::badge::badge;
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let user = client.get_user("ANKDDEV").await.unwrap();
+/// std::fs::write("codewars.svg", badge(&user)).unwrap();
+/// # }
+/// ```
+pub fn badge(user: &User) -> String {
+    let overall = &user.ranks.overall;
+    let honor = format!("{} honor", user.honor);
+    let segments = [
+        ("codewars", LABEL_COLOR, "#fff"),
+        (
+            overall.name.as_str(),
+            rank_color(&overall.color),
+            text_color(&overall.color),
+        ),
+        (honor.as_str(), DARK_COLOR, "#fff"),
+    ];
+    let widths: Vec<usize> = segments
+        .iter()
+        .map(|(text, _, _)| text_width(text) + 2 * PADDING)
+        .collect();
+    let width: usize = widths.iter().sum();
+    let label = format!("codewars: {}: {}", overall.name, honor);
+    let mut rects = String::new();
+    let mut texts = String::new();
+    let mut x = 0;
+    for ((text, fill, color), segment_width) in segments.iter().zip(&widths) {
+        rects.push_str(&format!(
+            "    <rect x=\"{}\" width=\"{}\" height=\"20\" fill=\"{}\"/>\n",
+            x, segment_width, fill
+        ));
+        texts.push_str(&format!(
+            "    <text x=\"{}\" y=\"14\" fill=\"{}\">{}</text>\n",
+            x + segment_width / 2,
+            color,
+            escape(text)
+        ));
+        x += segment_width;
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}\">\n\
+         \x20 <title>{label}</title>\n\
+         \x20 <linearGradient id=\"s\" x2=\"0\" y2=\"100%\">\n\
+         \x20   <stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/>\n\
+         \x20   <stop offset=\"1\" stop-opacity=\".1\"/>\n\
+         \x20 </linearGradient>\n\
+         \x20 <clipPath id=\"r\">\n\
+         \x20   <rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/>\n\
+         \x20 </clipPath>\n\
+         \x20 <g clip-path=\"url(#r)\">\n\
+         {rects}\
+         \x20   <rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/>\n\
+         \x20 </g>\n\
+         \x20 <g text-anchor=\"middle\" font-family=\"{FONT}\" font-size=\"11\">\n\
+         {texts}\
+         \x20 </g>\n\
+         </svg>\n",
+        label = escape(&label),
+    )
+}
+
+/// Render profile card with overall rank, honor and ranks in every language of the user
+///
+/// Languages are sorted from the highest score
+pub fn profile_card(user: &User) -> String {
+    let overall = &user.ranks.overall;
+    let mut languages: Vec<(&String, &Rank)> = user.ranks.languages.iter().collect();
+    languages.sort_by(|a, b| b.1.score.cmp(&a.1.score).then(a.0.cmp(b.0)));
+    let height = 70 + ROW_HEIGHT * languages.len() + 10;
+    let mut rows = String::new();
+    for (index, (language, rank)) in languages.iter().enumerate() {
+        let y = 70 + ROW_HEIGHT * index;
+        rows.push_str(&format!(
+            "  <text x=\"20\" y=\"{}\" fill=\"#fff\" font-size=\"12\">{}</text>\n",
+            y + 17,
+            escape(language)
+        ));
+        rows.push_str(&rank_pill(&rank.name, &rank.color, 180, y + 4));
+        rows.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"#aaa\" font-size=\"12\" text-anchor=\"end\">{}</text>\n",
+            CARD_WIDTH - 20,
+            y + 17,
+            rank.score
+        ));
+    }
+    let label = format!("{}: {}, {} honor", user.username, overall.name, user.honor);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CARD_WIDTH}\" height=\"{height}\" role=\"img\" aria-label=\"{label}\" font-family=\"{FONT}\">\n\
+         \x20 <title>{label}</title>\n\
+         \x20 <rect width=\"{CARD_WIDTH}\" height=\"{height}\" rx=\"6\" fill=\"{DARK_COLOR}\"/>\n\
+         \x20 <text x=\"20\" y=\"30\" fill=\"#fff\" font-size=\"16\" font-weight=\"bold\">{username}</text>\n\
+         {overall}\
+         \x20 <text x=\"20\" y=\"54\" fill=\"#aaa\" font-size=\"12\">{honor} honor, {completed} katas completed</text>\n\
+         {rows}\
+         </svg>\n",
+        label = escape(&label),
+        username = escape(&user.username),
+        overall = rank_pill(&overall.name, &overall.color, 180, 14),
+        honor = user.honor,
+        completed = user.code_challenges.total_completed,
+    )
+}
+
+// Render rank name in a rounded rectangle filled with the rank color
+fn rank_pill(name: &str, color: &Color, x: usize, y: usize) -> String {
+    let width = text_width(name) + PADDING;
+    format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"18\" rx=\"9\" fill=\"{}\"/>\n\
+         \x20 <text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"11\" text-anchor=\"middle\">{}</text>\n",
+        x,
+        y,
+        width,
+        rank_color(color),
+        x + width / 2,
+        y + 13,
+        text_color(color),
+        escape(name)
+    )
+}
+
+// Get color of text readable on the rank color
+fn text_color(color: &Color) -> &'static str {
+    match color {
+        Color::White | Color::Yellow => "#333",
+        _ => "#fff",
+    }
+}
+
+// Get approximate width of the text in pixels
+fn text_width(text: &str) -> usize {
+    text.chars().count() * CHAR_WIDTH
+}
+
+// Escape text for XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    //! Snapshot tests for badges
+    //!
+    //! Run tests with `UPDATE_SNAPSHOTS=1` environment variable to update snapshots.
+
+    use super::*;
+    use crate::fixtures::user;
+    use std::path::Path;
+
+    // Compare output with the snapshot, or update the snapshot
+    fn assert_snapshot(name: &str, output: &str) {
+        let path = Path::new("tests/snapshots").join(name);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, output).unwrap();
+        }
+        let snapshot = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(output, snapshot, "output differs from snapshot {}", name);
+    }
+
+    /// Test badge of user
+    #[test]
+    fn test_badge() {
+        assert_snapshot("badge.svg", &badge(&user()));
+    }
+
+    /// Test profile card of user
+    #[test]
+    fn test_profile_card() {
+        assert_snapshot("profile_card.svg", &profile_card(&user()));
+    }
+
+    /// Test colors of ranks
+    #[test]
+    fn test_colors() {
+        let mut user = user();
+        user.ranks.overall.color = Color::Red;
+        assert!(badge(&user).contains("fill=\"#bb432c\""));
+        user.ranks.overall.color = Color::White;
+        assert!(badge(&user).contains("fill=\"#333\">3 kyu</text>"));
+    }
+}
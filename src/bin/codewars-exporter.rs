@@ -0,0 +1,157 @@
+//! Prometheus exporter of Codewars users
+//!
+//! Usage:
+//! ```shell
+//! $ codewars-exporter <username>... [--listen <address>] [--interval <seconds>]
+//! ```
+//! Users are fetched every 5 minutes by default and metrics are served at
+//! `http://127.0.0.1:9898/metrics`. See [`codewars_api::metrics`] for exported gauges.
+
+use codewars_api::metrics::{UserMetrics, CONTENT_TYPE};
+use codewars_api::RestCodewarsClient;
+use std::process::ExitCode;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const USAGE: &str =
+    "Usage: codewars-exporter <username>... [--listen <address>] [--interval <seconds>]";
+
+/// Default address to serve metrics on
+const DEFAULT_LISTEN: &str = "127.0.0.1:9898";
+/// Default interval of fetching users
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+/// Maximum number of requests to Codewars sent at the same time
+const CONCURRENCY: usize = 4;
+/// Maximum size of a request head
+const MAX_REQUEST: usize = 8192;
+/// Maximum time of fetching all users, after which all of them are down until the next refresh
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(60);
+/// Maximum time of reading a request head
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Parse arguments, then fetch users periodically and serve metrics
+async fn run(args: Vec<String>) -> Result<(), String> {
+    let mut usernames = Vec::new();
+    let mut listen = DEFAULT_LISTEN.to_string();
+    let mut interval = DEFAULT_INTERVAL;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => listen = args.next().ok_or(USAGE)?,
+            "--interval" => {
+                let seconds = args.next().ok_or(USAGE)?;
+                let seconds: u64 = seconds.parse().map_err(|_| USAGE.to_string())?;
+                interval = Duration::from_secs(seconds.max(1));
+            }
+            _ => usernames.push(arg),
+        }
+    }
+    if usernames.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let listener = TcpListener::bind(&listen)
+        .await
+        .map_err(|err| err.to_string())?;
+    println!("Serving metrics at http://{}/metrics", listen);
+    let mut metrics = UserMetrics::new(&usernames.iter().map(String::as_str).collect::<Vec<_>>());
+    let rendered = Arc::new(RwLock::new(metrics.render()));
+    let published = Arc::clone(&rendered);
+    tokio::spawn(async move {
+        let client = RestCodewarsClient::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match tokio::time::timeout(REFRESH_TIMEOUT, metrics.refresh(&client, CONCURRENCY)).await
+            {
+                Ok(errors) => {
+                    for (username, err) in errors {
+                        eprintln!("Can't fetch {}: {}", username, err);
+                    }
+                }
+                Err(_) => {
+                    // Users are updated only after all of them are fetched
+                    eprintln!("Fetching users timed out");
+                    for username in &usernames {
+                        metrics.update(username, Err("timed out".to_string()));
+                    }
+                }
+            }
+            *published.write().unwrap() = metrics.render();
+        }
+    });
+    loop {
+        let (stream, _) = listener.accept().await.map_err(|err| err.to_string())?;
+        let rendered = Arc::clone(&rendered);
+        tokio::spawn(async move {
+            if let Err(err) = handle(stream, &rendered).await {
+                eprintln!("Can't handle request: {}", err);
+            }
+        });
+    }
+}
+
+// Respond to a single HTTP request
+async fn handle(mut stream: TcpStream, rendered: &RwLock<String>) -> std::io::Result<()> {
+    let request = tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "request timed out"))??;
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            ("200 OK", CONTENT_TYPE, rendered.read().unwrap().clone())
+        }
+        (Some("GET"), Some("/")) => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            "<a href=\"/metrics\">Metrics</a>\n".to_string(),
+        ),
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+// Read request head, body is ignored
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Vec<u8>> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    Ok(request)
+}
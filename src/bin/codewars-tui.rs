@@ -0,0 +1,467 @@
+//! Interactive terminal dashboard of a Codewars user
+//!
+//! Usage:
+//! ```shell
+//! $ codewars-tui <username> [--offline] [--refresh <seconds>] [--cache <directory>]
+//! ```
+//! Data is refreshed every 5 minutes by default and cached in `$XDG_CACHE_HOME/codewars`.
+//! If Codewars can't be reached, cached data is shown. With `--offline` only cached data is used.
+//!
+//! Keys:
+//! * `Tab`, `Left`, `Right` - switch tabs
+//! * `Up`, `Down` - select kata or scroll details
+//! * `/` - search katas by name, `Enter` or `Esc` to finish
+//! * `Enter` - open details of selected kata, `Esc` to close them
+//! * `r` - refresh data
+//! * `q` - quit
+
+use codewars_api::badge::rank_color;
+use codewars_api::dashboard::{fetch_dashboard, rank_progress, DashboardCache, DashboardData};
+use codewars_api::description::render::render_markdown;
+use codewars_api::rest_api::models::{CodeChallenge, Rank};
+use codewars_api::RestCodewarsClient;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Gauge, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "Usage: codewars-tui <username> [--offline] [--refresh <seconds>] [--cache <directory>]";
+
+/// Default interval of auto-refresh
+const DEFAULT_REFRESH: Duration = Duration::from_secs(300);
+/// Timeout of waiting for a key press, after which auto-refresh is checked
+const TICK: Duration = Duration::from_millis(250);
+/// Titles of tabs
+const TABS: [&str; 3] = ["Overview", "Completed", "Authored"];
+
+/// State of the dashboard
+struct App {
+    client: RestCodewarsClient,
+    cache: Option<DashboardCache>,
+    data: DashboardData,
+    // Use only cached data
+    offline: bool,
+    // Last refresh failed, so shown data may be outdated
+    stale: bool,
+    refresh_every: Duration,
+    last_refresh: Instant,
+    status: String,
+    tab: usize,
+    list: ListState,
+    search: String,
+    searching: bool,
+    // ID of the kata which details are shown
+    detail: Option<String>,
+    scroll: u16,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let app = match start(args).await {
+        Ok(app) => app,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, app).await;
+    ratatui::restore();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Parse arguments and load initial data
+async fn start(args: Vec<String>) -> Result<App, String> {
+    let mut username = None;
+    let mut offline = false;
+    let mut refresh_every = DEFAULT_REFRESH;
+    let mut cache = DashboardCache::default_directory().map(DashboardCache::new);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--offline" => offline = true,
+            "--refresh" => {
+                let seconds = args.next().ok_or(USAGE)?;
+                let seconds: u64 = seconds.parse().map_err(|_| USAGE.to_string())?;
+                refresh_every = Duration::from_secs(seconds.max(1));
+            }
+            "--cache" => cache = Some(DashboardCache::new(args.next().ok_or(USAGE)?)),
+            _ if username.is_none() => username = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let username = username.ok_or(USAGE)?;
+    let (cached, cache_error) = match cache.as_ref().map(|cache| cache.load(&username)) {
+        Some(Ok(cached)) => (cached, None),
+        // Unreadable cache is replaced with fresh data, so it is fatal only in offline mode
+        Some(Err(err)) if !offline => (None, Some(err)),
+        Some(Err(err)) => return Err(err),
+        None => (None, None),
+    };
+    let client = RestCodewarsClient::new();
+    let (data, stale, status) = if offline {
+        let data = cached.ok_or(format!("no cached data of {}", username))?;
+        (data, true, "offline mode".to_string())
+    } else {
+        let katas = cached
+            .as_ref()
+            .map(|data| data.katas.clone())
+            .unwrap_or_default();
+        match (fetch_dashboard(&client, &username, katas).await, cached) {
+            (Ok(data), _) => {
+                let status = match cache_error {
+                    Some(err) => format!("can't load cache: {}; {}", err, save(&cache, &data)),
+                    None => save(&cache, &data),
+                };
+                (data, false, status)
+            }
+            // Fall back to cached data
+            (Err(err), Some(data)) => (data, true, format!("showing cached data: {}", err)),
+            (Err(err), None) => return Err(err),
+        }
+    };
+    Ok(App {
+        client,
+        cache,
+        data,
+        offline,
+        stale,
+        refresh_every,
+        last_refresh: Instant::now(),
+        status,
+        tab: 0,
+        list: ListState::default(),
+        search: String::new(),
+        searching: false,
+        detail: None,
+        scroll: 0,
+    })
+}
+
+// Save data to cache and get status message
+fn save(cache: &Option<DashboardCache>, data: &DashboardData) -> String {
+    match cache.as_ref().map(|cache| cache.save(data)) {
+        Some(Err(err)) => format!("can't save cache: {}", err),
+        _ => "updated".to_string(),
+    }
+}
+
+// Handle events and draw dashboard until user quits
+async fn run(terminal: &mut DefaultTerminal, mut app: App) -> Result<(), String> {
+    loop {
+        terminal
+            .draw(|frame| draw(frame, &mut app))
+            .map_err(|err| err.to_string())?;
+        if !app.offline && app.last_refresh.elapsed() >= app.refresh_every {
+            app.refresh().await;
+        }
+        if !event::poll(TICK).map_err(|err| err.to_string())? {
+            continue;
+        }
+        let Event::Key(key) = event::read().map_err(|err| err.to_string())? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if app.searching {
+            match key.code {
+                KeyCode::Char(c) => app.search.push(c),
+                KeyCode::Backspace => {
+                    app.search.pop();
+                }
+                KeyCode::Esc => {
+                    app.search.clear();
+                    app.searching = false;
+                }
+                KeyCode::Enter => app.searching = false,
+                _ => {}
+            }
+            app.list.select(None);
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Tab | KeyCode::Right => app.switch_tab(1),
+            KeyCode::BackTab | KeyCode::Left => app.switch_tab(TABS.len() - 1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+            KeyCode::Char('/') if app.detail.is_none() && app.tab > 0 => app.searching = true,
+            KeyCode::Enter => app.open_selected().await,
+            KeyCode::Esc => {
+                app.detail = None;
+                app.scroll = 0;
+            }
+            KeyCode::Char('r') if !app.offline => app.refresh().await,
+            _ => {}
+        }
+    }
+}
+
+/// Implementation of App
+impl App {
+    // Fetch data again, keeping details of opened katas
+    async fn refresh(&mut self) {
+        self.last_refresh = Instant::now();
+        let katas = self.data.katas.clone();
+        match fetch_dashboard(&self.client, &self.data.user.username, katas).await {
+            Ok(data) => {
+                self.status = save(&self.cache, &data);
+                self.data = data;
+                self.stale = false;
+            }
+            Err(err) => {
+                self.status = format!("showing cached data: {}", err);
+                self.stale = true;
+            }
+        }
+    }
+
+    // Switch to the next tab, or to the previous one if step is the number of tabs minus one
+    fn switch_tab(&mut self, step: usize) {
+        if self.detail.is_none() {
+            self.tab = (self.tab + step) % TABS.len();
+            self.list.select(None);
+            self.search.clear();
+        }
+    }
+
+    // Get IDs and names of katas on the current tab matching the search
+    fn items(&self) -> Vec<(String, String)> {
+        let search = self.search.to_lowercase();
+        let items: Vec<(String, String)> = match self.tab {
+            1 => self
+                .data
+                .completed
+                .iter()
+                .map(|kata| {
+                    let date = kata.completed_at.get(..10).unwrap_or(&kata.completed_at);
+                    (kata.id.clone(), format!("{}  {}", date, kata.name))
+                })
+                .collect(),
+            2 => self
+                .data
+                .authored
+                .iter()
+                .map(|kata| {
+                    let rank = kata.rank_name.as_deref().unwrap_or("beta");
+                    (kata.id.clone(), format!("{:<6} {}", rank, kata.name))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        items
+            .into_iter()
+            .filter(|(_, name)| name.to_lowercase().contains(&search))
+            .collect()
+    }
+
+    // Move selection in the list or scroll details
+    fn move_selection(&mut self, step: i32) {
+        if self.detail.is_some() {
+            self.scroll = self.scroll.saturating_add_signed(step as i16);
+            return;
+        }
+        let count = self.items().len();
+        if count == 0 {
+            return;
+        }
+        let selected = match self.list.selected() {
+            Some(selected) => (selected as i32 + step).clamp(0, count as i32 - 1) as usize,
+            None => 0,
+        };
+        self.list.select(Some(selected));
+    }
+
+    // Open details of the selected kata, fetching them if they are not cached
+    async fn open_selected(&mut self) {
+        let Some(selected) = self.list.selected() else {
+            return;
+        };
+        let Some((id, _)) = self.items().into_iter().nth(selected) else {
+            return;
+        };
+        if !self.data.katas.contains_key(&id) {
+            if self.offline {
+                self.status = "kata is not cached".to_string();
+                return;
+            }
+            match self.client.get_kata(&id).await {
+                Ok(kata) => {
+                    self.data.katas.insert(id.clone(), kata);
+                    self.status = save(&self.cache, &self.data);
+                }
+                Err(err) => {
+                    self.status = err;
+                    return;
+                }
+            }
+        }
+        self.detail = Some(id);
+        self.scroll = 0;
+    }
+}
+
+// Convert rank color to terminal color
+fn color(rank: &Rank) -> Color {
+    let hex = rank_color(&rank.color).trim_start_matches('#');
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).unwrap_or(0);
+    Color::Rgb(channel(0), channel(2), channel(4))
+}
+
+// Draw the whole dashboard
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [tabs, body, status] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let user = &app.data.user;
+    frame.render_widget(
+        Tabs::new(TABS)
+            .select(app.tab)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(format!(" {} ", user.username))),
+        tabs,
+    );
+    match &app.detail {
+        Some(id) => {
+            if let Some(kata) = app.data.katas.get(id) {
+                draw_kata(frame, body, kata, app);
+            }
+        }
+        None if app.tab == 0 => draw_overview(frame, body, &app.data),
+        None => draw_list(frame, body, app),
+    }
+    let mut line = format!(
+        " q quit | Tab switch | / search | Enter open | r refresh | updated {} | {}",
+        app.data.fetched_at.format("%Y-%m-%d %H:%M"),
+        app.status
+    );
+    if app.stale {
+        line.insert_str(0, " OFFLINE |");
+    }
+    frame.render_widget(
+        Paragraph::new(line).style(Style::new().add_modifier(Modifier::REVERSED)),
+        status,
+    );
+}
+
+// Draw profile and progress bars of ranks
+fn draw_overview(frame: &mut Frame, area: Rect, data: &DashboardData) {
+    let user = &data.user;
+    let mut languages: Vec<_> = user.ranks.languages.iter().collect();
+    // Sort languages from the highest score
+    languages.sort_by(|a, b| b.1.score.cmp(&a.1.score).then(a.0.cmp(b.0)));
+    let mut constraints = vec![Constraint::Length(4), Constraint::Length(3)];
+    constraints.extend(languages.iter().map(|_| Constraint::Length(3)));
+    constraints.push(Constraint::Min(0));
+    let areas = Layout::vertical(constraints).split(area);
+    let profile = vec![
+        Line::from(format!(
+            "{}  |  clan: {}  |  honor: {}  |  position: {}",
+            user.name, user.clan, user.honor, user.leaderboard_position
+        )),
+        Line::from(format!(
+            "{} katas completed, {} authored",
+            user.code_challenges.total_completed, user.code_challenges.total_authored
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(profile).block(Block::bordered().title(" Profile ")),
+        areas[0],
+    );
+    let gauges = std::iter::once(("overall", &user.ranks.overall)).chain(
+        languages
+            .iter()
+            .map(|(language, rank)| (language.as_str(), *rank)),
+    );
+    for ((title, rank), area) in gauges.zip(areas.iter().skip(1)) {
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(format!(" {} ", title)))
+                .gauge_style(Style::new().fg(color(rank)))
+                .ratio(rank_progress(rank))
+                .label(format!("{} (score {})", rank.name, rank.score)),
+            *area,
+        );
+    }
+}
+
+// Draw list of katas on the current tab
+fn draw_list(frame: &mut Frame, area: Rect, app: &mut App) {
+    let items: Vec<ListItem> = app
+        .items()
+        .into_iter()
+        .map(|(_, name)| ListItem::new(name))
+        .collect();
+    let mut title = format!(" {} ", TABS[app.tab]);
+    if app.searching || !app.search.is_empty() {
+        title.push_str(&format!("/ {} ", app.search));
+    }
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+        area,
+        &mut app.list,
+    );
+}
+
+// Draw details of the kata with description for the user's best language
+fn draw_kata(frame: &mut Frame, area: Rect, kata: &CodeChallenge, app: &App) {
+    let languages = &app.data.user.ranks.languages;
+    let language = kata
+        .languages
+        .iter()
+        .filter_map(|language| languages.get(language).map(|rank| (language, rank.score)))
+        .max_by_key(|(_, score)| *score)
+        .map(|(language, _)| language.as_str())
+        .or(kata.languages.first().map(String::as_str))
+        .unwrap_or("");
+    let rank = kata
+        .rank
+        .as_ref()
+        .map(|rank| rank.name.as_str())
+        .unwrap_or("beta");
+    let mut lines = vec![
+        Line::from(format!("{} | {} | {}", rank, kata.category, kata.url)),
+        Line::from(format!("Tags: {}", kata.tags.join(", "))),
+        Line::from(format!("Languages: {}", kata.languages.join(", "))),
+        Line::from(format!(
+            "{} completed, {} stars, vote score {}",
+            kata.total_completed, kata.total_stars, kata.vote_score
+        )),
+        Line::from(""),
+    ];
+    lines.extend(
+        render_markdown(&kata.description, language)
+            .lines()
+            .map(|line| Line::from(line.to_string())),
+    );
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.scroll, 0))
+            .block(Block::bordered().title(format!(" {} ({}) ", kata.name, language))),
+        area,
+    );
+}
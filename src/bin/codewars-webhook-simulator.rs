@@ -0,0 +1,66 @@
+//! Binary that sends sample webhook events to a local endpoint
+//!
+//! Usage:
+//! ```shell
+//! $ codewars-webhook-simulator <url> <script> [--secret <secret>] [--user <username>]
+//! ```
+//! Secret can also be set with `CODEWARS_WEBHOOK_SECRET` environment variable.
+//! If `--user` is set, user is fetched from Codewars and events are built from it.
+
+use codewars_api::webhooks::simulator::{sample_user, EventBuilder, Step, WebhookSimulator};
+use codewars_api::RestCodewarsClient;
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "Usage: codewars-webhook-simulator <url> <script> [--secret <secret>] [--user <username>]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    match run(args).await {
+        Ok(sent) => {
+            println!("Sent {} events", sent);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Parse arguments, build events and send them
+async fn run(args: Vec<String>) -> Result<usize, String> {
+    let mut positional = Vec::new();
+    let mut secret = std::env::var("CODEWARS_WEBHOOK_SECRET").ok();
+    let mut username = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--secret" => secret = Some(args.next().ok_or(USAGE)?),
+            "--user" => username = Some(args.next().ok_or(USAGE)?),
+            _ => positional.push(arg),
+        }
+    }
+    let [url, script] = <[String; 2]>::try_from(positional).map_err(|_| USAGE.to_string())?;
+    let steps = Step::parse_script(&script)?;
+    // Build events from real user if username is set
+    let user = match username {
+        Some(username) => RestCodewarsClient::new().get_user(&username).await?,
+        None => sample_user("some_user"),
+    };
+    let events = EventBuilder::new(user).build_all(&steps);
+    let simulator = match secret {
+        Some(secret) => WebhookSimulator::with_secret(&url, &secret),
+        None => WebhookSimulator::new(&url),
+    };
+    for event in &events {
+        println!("Sending {} event: {}", event.event_name(), event.body());
+        simulator.send(event).await?;
+    }
+    Ok(events.len())
+}
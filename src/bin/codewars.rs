@@ -0,0 +1,421 @@
+//! Command-line interface for Codewars API
+//!
+//! Usage:
+//! ```shell
+//! $ codewars user <username>
+//! $ codewars kata <id|slug|url>
+//! $ codewars completed <username> [--all]
+//! $ codewars authored <username>
+//! $ codewars scaffold <id|slug|url> --language <language> [--directory <directory>]
+//! ```
+//! Every command accepts `--format table|json|yaml`.
+//!
+//! Exit codes:
+//! * 0 - success
+//! * 1 - other error
+//! * 2 - invalid arguments
+//! * 3 - user or kata not found
+//! * 4 - rate limited by Codewars
+//! * 5 - Codewars is unavailable or network error
+//! * 6 - invalid response from Codewars
+
+use clap::{Parser, Subcommand, ValueEnum};
+use codewars_api::rest_api::client::ErrorKind;
+use codewars_api::rest_api::models::{
+    AuthoredChallenge, CodeChallenge, CompletedChallenge, KataRef, User,
+};
+use codewars_api::scaffold::scaffold;
+use codewars_api::RestCodewarsClient;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Command-line interface for Codewars API
+#[derive(Parser)]
+#[command(name = "codewars", version, about)]
+struct Cli {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Output format
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// Human-readable table
+    Table,
+    /// JSON
+    Json,
+    /// YAML
+    Yaml,
+}
+
+/// Available commands
+#[derive(Subcommand)]
+enum Command {
+    /// Show info about user
+    User {
+        /// Username of the user
+        username: String,
+    },
+    /// Show info about kata
+    Kata {
+        /// ID, slug or URL of the kata
+        kata: KataRef,
+    },
+    /// List completed katas of user
+    Completed {
+        /// Username of the user
+        username: String,
+        /// List katas from all pages instead of the first one
+        #[arg(long)]
+        all: bool,
+    },
+    /// List katas authored by user
+    Authored {
+        /// Username of the user
+        username: String,
+    },
+    /// Create local workspace for solving kata
+    Scaffold {
+        /// ID, slug or URL of the kata
+        kata: KataRef,
+        /// Language to solve the kata in
+        #[arg(short, long)]
+        language: String,
+        /// Directory to create workspace in
+        #[arg(short, long, default_value = ".")]
+        directory: PathBuf,
+    },
+}
+
+/// Exit code for errors which don't have a specific code
+const EXIT_ERROR: u8 = 1;
+/// Exit code when user or kata is not found
+const EXIT_NOT_FOUND: u8 = 3;
+/// Exit code when Codewars rate limited requests
+const EXIT_RATE_LIMITED: u8 = 4;
+/// Exit code when Codewars is unavailable or request can't be sent
+const EXIT_UNAVAILABLE: u8 = 5;
+/// Exit code when response of Codewars can't be decoded
+const EXIT_INVALID_RESPONSE: u8 = 6;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(output) => {
+            println!("{}", output.trim_end());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            ExitCode::from(exit_code(&err))
+        }
+    }
+}
+
+// Run command and return its output
+async fn run(cli: Cli) -> Result<String, String> {
+    let client = RestCodewarsClient::new();
+    match cli.command {
+        Command::User { username } => {
+            let user = client.get_user(&username).await?;
+            render(cli.format, &user, user_table)
+        }
+        Command::Kata { kata } => {
+            let kata = client.get_kata(kata.as_str()).await?;
+            render(cli.format, &kata, kata_table)
+        }
+        Command::Completed { username, all } => {
+            let completed = if all {
+                client.get_all_completed_challenges(&username).await?
+            } else {
+                client
+                    .get_completed_challenges_first_page(&username)
+                    .await?
+                    .data
+            };
+            render(cli.format, &completed, |completed| {
+                completed_table(completed)
+            })
+        }
+        Command::Authored { username } => {
+            let authored = client.get_authored_challenges(&username).await?.data;
+            render(cli.format, &authored, |authored| authored_table(authored))
+        }
+        Command::Scaffold {
+            kata,
+            language,
+            directory,
+        } => {
+            let files = scaffold(&client, &kata, &language, directory).await?;
+            render(cli.format, &files, |files| {
+                files
+                    .iter()
+                    .map(|file| format!("{}\n", file.display()))
+                    .collect()
+            })
+        }
+    }
+}
+
+// Map error message of the client to exit code
+fn exit_code(err: &str) -> u8 {
+    match ErrorKind::of(err) {
+        ErrorKind::NotFound => EXIT_NOT_FOUND,
+        ErrorKind::RateLimited => EXIT_RATE_LIMITED,
+        ErrorKind::Unavailable => EXIT_UNAVAILABLE,
+        ErrorKind::InvalidResponse => EXIT_INVALID_RESPONSE,
+        ErrorKind::Other => EXIT_ERROR,
+    }
+}
+
+// Render value in the format, using the function for tables
+fn render<T: Serialize>(
+    format: Format,
+    value: &T,
+    table: impl Fn(&T) -> String,
+) -> Result<String, String> {
+    match format {
+        Format::Table => Ok(table(value)),
+        Format::Json => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+        Format::Yaml => serde_yaml::to_string(value).map_err(|err| err.to_string()),
+    }
+}
+
+// Render user as list of fields and table of language ranks
+fn user_table(user: &User) -> String {
+    let mut languages: Vec<_> = user.ranks.languages.iter().collect();
+    // Sort languages from the highest score
+    languages.sort_by(|a, b| b.1.score.cmp(&a.1.score).then(a.0.cmp(b.0)));
+    let mut output = fields(&[
+        ("Username", user.username.clone()),
+        ("Name", user.name.clone()),
+        ("Clan", user.clan.clone()),
+        ("Honor", user.honor.to_string()),
+        (
+            "Leaderboard position",
+            user.leaderboard_position.to_string(),
+        ),
+        (
+            "Overall rank",
+            format!(
+                "{} (score {})",
+                user.ranks.overall.name, user.ranks.overall.score
+            ),
+        ),
+        (
+            "Authored katas",
+            user.code_challenges.total_authored.to_string(),
+        ),
+        (
+            "Completed katas",
+            user.code_challenges.total_completed.to_string(),
+        ),
+        ("Skills", user.skills.join(", ")),
+    ]);
+    output.push('\n');
+    output.push_str(&table(
+        &["Language", "Rank", "Score"],
+        languages
+            .into_iter()
+            .map(|(language, rank)| {
+                vec![language.clone(), rank.name.clone(), rank.score.to_string()]
+            })
+            .collect(),
+    ));
+    output
+}
+
+// Render kata as list of fields
+fn kata_table(kata: &CodeChallenge) -> String {
+    fields(&[
+        ("ID", kata.id.clone()),
+        ("Name", kata.name.clone()),
+        ("Slug", kata.slug.clone()),
+        ("URL", kata.url.clone()),
+        ("Category", kata.category.clone()),
+        (
+            "Rank",
+            kata.rank
+                .as_ref()
+                .map(|rank| rank.name.clone())
+                .unwrap_or_else(|| "beta".to_string()),
+        ),
+        ("Tags", kata.tags.join(", ")),
+        ("Languages", kata.languages.join(", ")),
+        ("Author", kata.created_by.username.clone()),
+        ("Published at", kata.published_at.clone()),
+        ("Completed", kata.total_completed.to_string()),
+        ("Attempts", kata.total_attempts.to_string()),
+        ("Stars", kata.total_stars.to_string()),
+        ("Vote score", kata.vote_score.to_string()),
+    ])
+}
+
+// Render completed katas as table
+fn completed_table(completed: &[CompletedChallenge]) -> String {
+    table(
+        &["Name", "Slug", "Completed at", "Languages"],
+        completed
+            .iter()
+            .map(|kata| {
+                vec![
+                    kata.name.clone(),
+                    kata.slug.clone(),
+                    kata.completed_at.clone(),
+                    kata.languages().join(", "),
+                ]
+            })
+            .collect(),
+    )
+}
+
+// Render authored katas as table
+fn authored_table(authored: &[AuthoredChallenge]) -> String {
+    table(
+        &["ID", "Name", "Rank", "Languages"],
+        authored
+            .iter()
+            .map(|kata| {
+                vec![
+                    kata.id.clone(),
+                    kata.name.clone(),
+                    kata.rank_name.clone().unwrap_or_else(|| "beta".to_string()),
+                    kata.languages.join(", "),
+                ]
+            })
+            .collect(),
+    )
+}
+
+// Render fields as aligned `name: value` lines
+fn fields(fields: &[(&str, String)]) -> String {
+    let width = fields
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    fields
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{:<width$}  {}\n",
+                format!("{}:", name),
+                value,
+                width = width + 1
+            )
+        })
+        .collect()
+}
+
+// Render rows as table with aligned columns
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| -> String {
+        let mut line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.truncate(line.trim_end().len());
+        line.push('\n');
+        line
+    };
+    let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut output = line(headers.to_vec());
+    output.push_str(&line(separators.iter().map(String::as_str).collect()));
+    for row in &rows {
+        output.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for command-line interface
+
+    use super::*;
+    use std::path::Path;
+
+    /// Test mapping errors to exit codes
+    #[test]
+    fn test_exit_code() {
+        for (err, code) in [
+            ("unexpected status code: 404 Not Found", EXIT_NOT_FOUND),
+            (
+                "unexpected status code: 429 Too Many Requests",
+                EXIT_RATE_LIMITED,
+            ),
+            (
+                "unexpected status code: 503 Service Unavailable",
+                EXIT_UNAVAILABLE,
+            ),
+            ("unexpected status code: 400 Bad Request", EXIT_ERROR),
+            (
+                "error sending request for url (https://www.codewars.com/)",
+                EXIT_UNAVAILABLE,
+            ),
+            ("error decoding response body", EXIT_INVALID_RESPONSE),
+            ("invalid language", EXIT_ERROR),
+        ] {
+            assert_eq!(exit_code(err), code, "wrong exit code for {}", err);
+        }
+    }
+
+    /// Test parsing arguments
+    #[test]
+    fn test_parse_arguments() {
+        let cli = Cli::try_parse_from([
+            "codewars",
+            "kata",
+            "https://www.codewars.com/kata/valid-braces/train/rust",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert!(matches!(cli.format, Format::Json));
+        assert!(matches!(cli.command, Command::Kata { kata } if kata.as_str() == "valid-braces"));
+
+        let cli = Cli::try_parse_from(["codewars", "completed", "some_user", "--all"]).unwrap();
+        assert!(matches!(cli.format, Format::Table));
+        assert!(
+            matches!(cli.command, Command::Completed { username, all: true } if username == "some_user")
+        );
+
+        let cli =
+            Cli::try_parse_from(["codewars", "scaffold", "valid-braces", "-l", "rust"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Scaffold { language, directory, .. }
+                if language == "rust" && directory == Path::new(".")
+        ));
+    }
+
+    /// Test rejecting invalid arguments with exit code 2
+    #[test]
+    fn test_invalid_arguments() {
+        for args in [
+            &["codewars"][..],
+            &["codewars", "user"],
+            &["codewars", "user", "some_user", "--format", "xml"],
+            &["codewars", "scaffold", "valid-braces"],
+            &["codewars", "unknown"],
+        ] {
+            let err = Cli::try_parse_from(args).err().unwrap();
+            assert_eq!(err.exit_code(), 2, "arguments {:?} are accepted", args);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Aggregate report about members of a clan
+//!
+//! Codewars API has no clan endpoint, so the report is built from a list of member usernames.
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::User;
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+/// Options of clan report
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ClanReportOptions {
+    /// Maximum number of requests sent at the same time
+    pub concurrency: usize,
+    /// Number of top members listed for each language
+    pub top_members: usize,
+}
+
+/// Default options send 4 requests at a time and list top 3 members per language
+impl Default for ClanReportOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            top_members: 3,
+        }
+    }
+}
+
+/// Represents number of members with a rank
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct RankCount {
+    /// Rank in integer. [-8, -1] maps to kyu, [1, 8] maps to dan
+    pub rank: i8,
+    /// Name of the rank
+    pub name: String,
+    /// Number of members with the rank
+    pub count: u64,
+}
+
+/// Represents score of a member in a language
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct MemberScore {
+    /// Username of the member
+    pub username: String,
+    /// Name of the rank of the member in the language
+    pub rank: String,
+    /// Score of the member in the language
+    pub score: u64,
+}
+
+/// Represents a member whose clan doesn't match the reported clan
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ClanMismatch {
+    /// Username of the member
+    pub username: String,
+    /// Clan of the member on Codewars
+    pub clan: String,
+}
+
+/// Represents an aggregate report about members of a clan
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ClanReport {
+    /// Name of the clan
+    pub clan: String,
+    /// Number of fetched members
+    pub members: u64,
+    /// Total honor of fetched members
+    pub total_honor: u64,
+    /// Number of members with each overall rank, from the lowest rank
+    pub rank_histogram: Vec<RankCount>,
+    /// Members with the highest score in each language, from the highest score
+    pub top_members: BTreeMap<String, Vec<MemberScore>>,
+    /// Members whose clan no longer matches the reported clan
+    pub mismatched: Vec<ClanMismatch>,
+    /// Errors of members which couldn't be fetched, keyed by username
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Implementation of ClanReport
+impl ClanReport {
+    /// Build report from fetched members
+    ///
+    /// Clans are compared ignoring case and surrounding whitespace. Repeated members are counted
+    /// once, ignoring case of usernames
+    ///
+    /// # Arguments:
+    /// * clan (&str) - name of the clan
+    /// * members (&[User]) - fetched members of the clan
+    /// * top_members (usize) - number of top members listed for each language
+    pub fn new(clan: &str, members: &[User], top_members: usize) -> Self {
+        let mut usernames = HashSet::new();
+        let members: Vec<&User> = members
+            .iter()
+            .filter(|member| usernames.insert(member.username.to_lowercase()))
+            .collect();
+        let mut histogram: BTreeMap<i8, RankCount> = BTreeMap::new();
+        let mut languages: BTreeMap<String, Vec<MemberScore>> = BTreeMap::new();
+        for member in &members {
+            let overall = &member.ranks.overall;
+            histogram
+                .entry(overall.rank)
+                .or_insert_with(|| RankCount {
+                    rank: overall.rank,
+                    name: overall.name.clone(),
+                    count: 0,
+                })
+                .count += 1;
+            for (language, rank) in &member.ranks.languages {
+                languages
+                    .entry(language.clone())
+                    .or_default()
+                    .push(MemberScore {
+                        username: member.username.clone(),
+                        rank: rank.name.clone(),
+                        score: rank.score,
+                    });
+            }
+        }
+        for scores in languages.values_mut() {
+            scores.sort_by(|a, b| b.score.cmp(&a.score).then(a.username.cmp(&b.username)));
+            scores.truncate(top_members);
+        }
+        Self {
+            clan: clan.to_string(),
+            members: members.len() as u64,
+            total_honor: members.iter().map(|member| member.honor).sum(),
+            rank_histogram: histogram.into_values().collect(),
+            top_members: languages,
+            mismatched: members
+                .iter()
+                .filter(|member| !member.clan.trim().eq_ignore_ascii_case(clan.trim()))
+                .map(|member| ClanMismatch {
+                    username: member.username.clone(),
+                    clan: member.clan.clone(),
+                })
+                .collect(),
+            errors: BTreeMap::new(),
+        }
+    }
+
+    /// Render report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let escape = |cell: &str| cell.replace('|', "\\|");
+        let mut output = format!("# Clan {}\n\n", self.clan.trim());
+        writeln!(output, "- Members: {}", self.members).unwrap();
+        writeln!(output, "- Total honor: {}", self.total_honor).unwrap();
+        output.push_str("\n## Ranks\n\n| Rank | Members |\n| --- | --- |\n");
+        for rank in &self.rank_histogram {
+            writeln!(output, "| {} | {} |", rank.name, rank.count).unwrap();
+        }
+        output.push_str(
+            "\n## Top members\n\n| Language | Member | Rank | Score |\n| --- | --- | --- | --- |\n",
+        );
+        for (language, scores) in &self.top_members {
+            for score in scores {
+                writeln!(
+                    output,
+                    "| {} | {} | {} | {} |",
+                    language,
+                    escape(&score.username),
+                    score.rank,
+                    score.score
+                )
+                .unwrap();
+            }
+        }
+        if !self.mismatched.is_empty() {
+            output.push_str("\n## Clan mismatches\n\n| Member | Clan |\n| --- | --- |\n");
+            for mismatch in &self.mismatched {
+                writeln!(
+                    output,
+                    "| {} | {} |",
+                    escape(&mismatch.username),
+                    escape(&mismatch.clan)
+                )
+                .unwrap();
+            }
+        }
+        if !self.errors.is_empty() {
+            output.push_str("\n## Errors\n\n| Member | Error |\n| --- | --- |\n");
+            for (username, err) in &self.errors {
+                writeln!(output, "| {} | {} |", escape(username), escape(err)).unwrap();
+            }
+        }
+        output
+    }
+}
+
+/// Fetch members of the clan and build report about them
+///
+/// Members which couldn't be fetched are listed in [`ClanReport::errors`]. Usernames are
+/// case-insensitive, so repeated usernames are fetched once
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * clan (&str) - name of the clan
+/// * usernames (&[&str]) - usernames of members of the clan
+/// * options (ClanReportOptions) - options of the report
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::clan::{clan_report, ClanReportOptions};
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let report = clan_report(
+///     &client,
+///     "some clan",
+///     &["ANKDDEV", "Dentzil"],
+///     ClanReportOptions::default(),
+/// )
+/// .await;
+/// println!("{}", report.to_markdown());
+/// println!("{}", serde_json::to_string(&report).unwrap());
+/// # }
+/// ```
+pub async fn clan_report(
+    client: &dyn CodewarsApi,
+    clan: &str,
+    usernames: &[&str],
+    options: ClanReportOptions,
+) -> ClanReport {
+    let mut distinct = HashSet::new();
+    let usernames: Vec<&str> = usernames
+        .iter()
+        .copied()
+        .filter(|username| distinct.insert(username.trim().to_lowercase()))
+        .collect();
+    let mut results: Vec<(usize, &str, Result<User, String>)> = stream::iter(
+        usernames.into_iter().enumerate(),
+    )
+    .map(|(index, username)| async move { (index, username, client.get_user(username).await) })
+    .buffer_unordered(options.concurrency.max(1))
+    .collect()
+    .await;
+    // Keep members in the order they were passed
+    results.sort_by_key(|(index, _, _)| *index);
+    let mut members = Vec::new();
+    let mut errors = BTreeMap::new();
+    for (_, username, result) in results {
+        match result {
+            Ok(user) => members.push(user),
+            Err(err) => {
+                errors.insert(username.to_string(), err);
+            }
+        }
+    }
+    ClanReport {
+        errors,
+        ..ClanReport::new(clan, &members, options.top_members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for clan report
+
+    use super::*;
+    use crate::fixtures::user;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    /// Test fetching members and building report
+    #[tokio::test]
+    async fn test_clan_report() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut other = user();
+        other.username = "other_user".to_string();
+        other.honor = 100;
+        other.clan = "Other Clan".to_string();
+        other.ranks.overall.rank = -6;
+        other.ranks.overall.name = "6 kyu".to_string();
+        other.ranks.languages.get_mut("ruby").unwrap().score = 5000;
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&user()).unwrap())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/other_user")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&other).unwrap())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+        let options = ClanReportOptions {
+            concurrency: 2,
+            top_members: 1,
+        };
+        let report = clan_report(
+            &client,
+            " Some Clan",
+            &["some_user", "missing", "other_user"],
+            options,
+        )
+        .await;
+        assert_eq!(report.members, 2);
+        assert_eq!(report.total_honor, 644);
+        assert_eq!(
+            report
+                .rank_histogram
+                .iter()
+                .map(|rank| (rank.name.as_str(), rank.count))
+                .collect::<Vec<_>>(),
+            vec![("6 kyu", 1), ("3 kyu", 1)]
+        );
+        assert_eq!(report.top_members["ruby"].len(), 1);
+        assert_eq!(report.top_members["ruby"][0].username, "other_user");
+        assert_eq!(report.mismatched.len(), 1);
+        assert_eq!(report.mismatched[0].clan, "Other Clan");
+        assert!(report.errors["missing"].starts_with("unexpected status code: 404"));
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Clan Some Clan\n\n- Members: 2\n- Total honor: 644\n"));
+        assert!(markdown.contains("| ruby | other_user | 4 kyu | 5000 |\n"));
+        assert!(markdown.contains("| other_user | Other Clan |\n"));
+    }
+
+    /// Test that repeated members are fetched and counted once
+    #[tokio::test]
+    async fn test_repeated_members() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mock = server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&user()).unwrap())
+            .expect(1)
+            .create_async()
+            .await;
+        let report = clan_report(
+            &client,
+            "Some Clan",
+            &["some_user", "SOME_USER", "some_user"],
+            ClanReportOptions::default(),
+        )
+        .await;
+        mock.assert_async().await;
+        assert_eq!(report.members, 1);
+        assert_eq!(report.total_honor, 544);
+        assert!(report.errors.is_empty());
+        let mut renamed = user();
+        renamed.username = "Some_User".to_string();
+        let report = ClanReport::new("Some Clan", &[user(), renamed], 5);
+        assert_eq!(report.members, 1);
+        assert_eq!(report.rank_histogram[0].count, 1);
+        assert_eq!(report.top_members["ruby"].len(), 1);
+    }
+}
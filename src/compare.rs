@@ -0,0 +1,359 @@
+//! Side-by-side comparison of several users
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{CompletedChallenge, Rank, User};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Represents main info about a compared user
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct UserSummary {
+    /// Username of the user
+    pub username: String,
+    /// Honor of the user
+    pub honor: u64,
+    /// Position of the user on the overall leaderboard
+    pub leaderboard_position: u64,
+    /// Overall rank of the user
+    pub overall_rank: Rank,
+}
+
+/// Represents a kata in comparison
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+pub struct KataSummary {
+    /// ID of the kata
+    pub id: String,
+    /// Name of the kata
+    pub name: String,
+    /// Slug of the kata
+    pub slug: String,
+}
+
+/// Represents comparison of completion histories
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct CompletedComparison {
+    /// Katas completed by every user
+    pub completed_by_all: Vec<KataSummary>,
+    /// Katas completed only by one user and by none of others, keyed by username
+    ///
+    /// With more than two users, use [`CompletedComparison::completed_by_but_not`] for
+    /// differences between two of them
+    pub completed_only_by: BTreeMap<String, Vec<KataSummary>>,
+    /// Katas completed by each user, keyed by username
+    pub completed_by_user: BTreeMap<String, Vec<KataSummary>>,
+    /// IDs of katas completed by at least one user
+    pub completed_by_any: BTreeSet<String>,
+}
+
+/// Implementation of CompletedComparison
+impl CompletedComparison {
+    /// Get katas completed by the user, but not by the other user
+    ///
+    /// Returns empty list if any of users was not compared
+    ///
+    /// # Arguments:
+    /// * username (&str) - username of the user who completed katas
+    /// * other (&str) - username of the user who didn't complete katas
+    pub fn completed_by_but_not(&self, username: &str, other: &str) -> Vec<KataSummary> {
+        let (Some(completed), Some(other_completed)) = (
+            self.completed_by_user.get(username),
+            self.completed_by_user.get(other),
+        ) else {
+            return Vec::new();
+        };
+        completed
+            .iter()
+            .filter(|kata| other_completed.binary_search(kata).is_err())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Represents comparison of several users
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Comparison {
+    /// Compared users in the order they were passed
+    pub users: Vec<UserSummary>,
+    /// Ranks of users in each language, keyed by language and then by username
+    pub languages: BTreeMap<String, BTreeMap<String, Rank>>,
+    /// Languages in which every user has a rank
+    pub shared_languages: Vec<String>,
+    /// Comparison of completion histories, if they were fetched
+    pub completed: Option<CompletedComparison>,
+}
+
+/// Implementation of Comparison
+impl Comparison {
+    /// Compare users without their completion histories
+    ///
+    /// If the same user is passed several times, ignoring case of usernames, only the first one
+    /// is compared
+    pub fn new(users: &[User]) -> Self {
+        let users = distinct_users(users);
+        let mut languages: BTreeMap<String, BTreeMap<String, Rank>> = BTreeMap::new();
+        for user in &users {
+            for (language, rank) in &user.ranks.languages {
+                languages
+                    .entry(language.clone())
+                    .or_default()
+                    .insert(user.username.clone(), rank.clone());
+            }
+        }
+        let shared_languages = languages
+            .iter()
+            .filter(|(_, ranks)| !users.is_empty() && ranks.len() == users.len())
+            .map(|(language, _)| language.clone())
+            .collect();
+        Self {
+            users: users
+                .iter()
+                .map(|user| UserSummary {
+                    username: user.username.clone(),
+                    honor: user.honor,
+                    leaderboard_position: user.leaderboard_position,
+                    overall_rank: user.ranks.overall.clone(),
+                })
+                .collect(),
+            languages,
+            shared_languages,
+            completed: None,
+        }
+    }
+
+    /// Compare users with their completion histories
+    ///
+    /// If the same user is passed several times, ignoring case of usernames, only the first one
+    /// and their completed katas are compared
+    ///
+    /// # Arguments:
+    /// * users (&[User]) - users to compare
+    /// * completed (&[Vec<CompletedChallenge>]) - completed katas of every user, in the same order as users
+    ///
+    /// # Errors:
+    /// * `expected completed katas for {expected} users, got {actual}` - If lengths of slices differ
+    pub fn with_completed(
+        users: &[User],
+        completed: &[Vec<CompletedChallenge>],
+    ) -> Result<Self, String> {
+        if users.len() != completed.len() {
+            return Err(format!(
+                "expected completed katas for {} users, got {}",
+                users.len(),
+                completed.len()
+            ));
+        }
+        // Katas completed by each user and users which completed each kata
+        let mut completed_by_user: BTreeMap<String, BTreeSet<KataSummary>> = BTreeMap::new();
+        let mut completed_by: BTreeMap<KataSummary, BTreeSet<&str>> = BTreeMap::new();
+        let mut usernames = BTreeSet::new();
+        for (user, completed) in users.iter().zip(completed) {
+            if !usernames.insert(user.username.to_lowercase()) {
+                continue;
+            }
+            let user_completed = completed_by_user.entry(user.username.clone()).or_default();
+            for kata in completed {
+                let summary = KataSummary {
+                    id: kata.id.clone(),
+                    name: kata.name.clone(),
+                    slug: kata.slug.clone(),
+                };
+                user_completed.insert(summary.clone());
+                completed_by
+                    .entry(summary)
+                    .or_default()
+                    .insert(&user.username);
+            }
+        }
+        let mut completed_only_by: BTreeMap<String, Vec<KataSummary>> = completed_by_user
+            .keys()
+            .map(|username| (username.clone(), Vec::new()))
+            .collect();
+        let mut completed_by_all = Vec::new();
+        for (kata, usernames) in &completed_by {
+            if usernames.len() == completed_by_user.len() {
+                completed_by_all.push(kata.clone());
+            } else if usernames.len() == 1 {
+                let username = usernames.iter().next().unwrap();
+                completed_only_by
+                    .get_mut(*username)
+                    .unwrap()
+                    .push(kata.clone());
+            }
+        }
+        Ok(Self {
+            completed: Some(CompletedComparison {
+                completed_by_all,
+                completed_only_by,
+                completed_by_user: completed_by_user
+                    .into_iter()
+                    .map(|(username, katas)| (username, katas.into_iter().collect()))
+                    .collect(),
+                completed_by_any: completed_by.into_keys().map(|kata| kata.id).collect(),
+            }),
+            ..Self::new(users)
+        })
+    }
+
+    /// Check if none of users completed the kata
+    ///
+    /// Returns `None` if completion histories were not fetched
+    pub fn is_new_to_all(&self, kata_id: &str) -> Option<bool> {
+        self.completed
+            .as_ref()
+            .map(|completed| !completed.completed_by_any.contains(kata_id))
+    }
+}
+
+// Remove repeated users ignoring case of usernames, keeping the first of them
+fn distinct_users(users: &[User]) -> Vec<&User> {
+    let mut usernames = BTreeSet::new();
+    users
+        .iter()
+        .filter(|user| usernames.insert(user.username.to_lowercase()))
+        .collect()
+}
+
+/// Fetch users and compare them
+///
+/// Repeated usernames are fetched and compared once, ignoring case
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * usernames (&[&str]) - usernames of users to compare
+/// * include_completed (bool) - whether to fetch and compare full completion histories
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::compare::compare_users;
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let comparison = compare_users(&client, &["ANKDDEV", "Dentzil"], true).await.unwrap();
+/// println!("Shared languages: {}", comparison.shared_languages.join(", "));
+/// # }
+/// ```
+pub async fn compare_users(
+    client: &dyn CodewarsApi,
+    usernames: &[&str],
+    include_completed: bool,
+) -> Result<Comparison, String> {
+    let mut users = Vec::new();
+    let mut completed = Vec::new();
+    let mut fetched = BTreeSet::new();
+    for username in usernames {
+        if !fetched.insert(username.to_lowercase()) {
+            continue;
+        }
+        users.push(client.get_user(username).await?);
+        if include_completed {
+            completed.push(client.get_all_completed_challenges(username).await?);
+        }
+    }
+    match include_completed {
+        true => Comparison::with_completed(&users, &completed),
+        false => Ok(Comparison::new(&users)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for comparison of users
+
+    use super::*;
+    use crate::fixtures::{completed_ids as completed, user_with as user};
+
+    /// Test comparing users with completion histories
+    #[test]
+    fn test_compare_users() {
+        let users = [
+            user("alice", &["ruby", "javascript"]),
+            user("bob", &["ruby", "coffeescript"]),
+        ];
+        let comparison = Comparison::with_completed(
+            &users,
+            &[completed(&["a", "b", "c"]), completed(&["b", "d"])],
+        )
+        .unwrap();
+        assert_eq!(comparison.users[1].username, "bob");
+        assert_eq!(comparison.shared_languages, vec!["ruby"]);
+        assert_eq!(
+            comparison.languages["javascript"]
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["alice"]
+        );
+        let completed = comparison.completed.as_ref().unwrap();
+        let ids =
+            |katas: &[KataSummary]| katas.iter().map(|kata| kata.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&completed.completed_by_all), vec!["b"]);
+        assert_eq!(ids(&completed.completed_only_by["alice"]), vec!["a", "c"]);
+        assert_eq!(ids(&completed.completed_only_by["bob"]), vec!["d"]);
+        assert_eq!(comparison.is_new_to_all("e"), Some(true));
+        assert_eq!(comparison.is_new_to_all("d"), Some(false));
+    }
+
+    /// Test that mismatched number of histories is rejected
+    #[test]
+    fn test_mismatched_histories() {
+        let users = [user("alice", &[])];
+        assert!(Comparison::with_completed(&users, &[]).is_err());
+        assert_eq!(Comparison::new(&users).is_new_to_all("a"), None);
+    }
+
+    /// Test that only the first of repeated users is compared, with and without histories
+    #[test]
+    fn test_repeated_users() {
+        let users = [
+            user("alice", &["ruby"]),
+            user("bob", &["ruby", "javascript"]),
+            user("Alice", &["ruby", "javascript"]),
+        ];
+        let comparison = Comparison::with_completed(
+            &users,
+            &[completed(&["a", "b"]), completed(&["b"]), completed(&["c"])],
+        )
+        .unwrap();
+        assert_eq!(comparison.users.len(), 2);
+        assert_eq!(comparison.users[0].username, "alice");
+        assert_eq!(comparison.shared_languages, vec!["ruby"]);
+        assert_eq!(Comparison::new(&users).users, comparison.users);
+        let completed = comparison.completed.as_ref().unwrap();
+        let ids =
+            |katas: &[KataSummary]| katas.iter().map(|kata| kata.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&completed.completed_by_all), vec!["b"]);
+        assert_eq!(ids(&completed.completed_only_by["alice"]), vec!["a"]);
+        assert!(completed.completed_only_by["bob"].is_empty());
+        assert_eq!(comparison.is_new_to_all("c"), Some(true));
+    }
+
+    /// Test differences between two of several users
+    #[test]
+    fn test_pairwise_differences() {
+        let users = [user("alice", &[]), user("bob", &[]), user("carol", &[])];
+        let comparison = Comparison::with_completed(
+            &users,
+            &[
+                completed(&["a", "b"]),
+                completed(&["b", "c"]),
+                completed(&["a", "c"]),
+            ],
+        )
+        .unwrap();
+        let completed = comparison.completed.as_ref().unwrap();
+        // Every kata is completed by two users, so none is completed only by one of them
+        assert!(completed.completed_only_by.values().all(Vec::is_empty));
+        let ids =
+            |katas: Vec<KataSummary>| katas.into_iter().map(|kata| kata.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(completed.completed_by_but_not("alice", "bob")),
+            vec!["a"]
+        );
+        assert_eq!(
+            ids(completed.completed_by_but_not("bob", "alice")),
+            vec!["c"]
+        );
+        assert!(completed.completed_by_but_not("alice", "dave").is_empty());
+    }
+}
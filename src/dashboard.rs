@@ -0,0 +1,203 @@
+//! Data of the interactive terminal dashboard
+//!
+//! Available with `tui` feature. Fetched data is saved to [`DashboardCache`], so the dashboard
+//! can be used offline.
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{AuthoredChallenge, CodeChallenge, CompletedChallenge, Rank, User};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Scores needed for ranks from 8 kyu to 5 dan
+const RANK_SCORES: [(i8, u64); 13] = [
+    (-8, 0),
+    (-7, 20),
+    (-6, 76),
+    (-5, 229),
+    (-4, 643),
+    (-3, 1768),
+    (-2, 4829),
+    (-1, 13147),
+    (1, 35759),
+    (2, 97225),
+    (3, 264305),
+    (4, 718477),
+    (5, 1953045),
+];
+
+/// Represents data shown on the dashboard
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct DashboardData {
+    /// Date and time when the user was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// Profile of the user
+    pub user: User,
+    /// Recently completed katas, from the first page of completed katas
+    pub completed: Vec<CompletedChallenge>,
+    /// Katas authored by the user
+    pub authored: Vec<AuthoredChallenge>,
+    /// Details of opened katas keyed by ID
+    pub katas: HashMap<String, CodeChallenge>,
+}
+
+/// Cache of dashboard data, one JSON file per user
+#[derive(Debug, Clone)]
+pub struct DashboardCache {
+    directory: PathBuf,
+}
+
+/// Implementation of DashboardCache
+impl DashboardCache {
+    /// Create cache in the directory. It is created when data is saved
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Get default cache directory, `$XDG_CACHE_HOME/codewars` or `$HOME/.cache/codewars`
+    pub fn default_directory() -> Option<PathBuf> {
+        let cache = match std::env::var_os("XDG_CACHE_HOME") {
+            Some(cache) => PathBuf::from(cache),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+        };
+        Some(cache.join("codewars"))
+    }
+
+    // Get path of the cache file of the user
+    fn path(&self, username: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", username))
+    }
+
+    /// Load cached data of the user
+    ///
+    /// Returns `Ok(None)` if there is no cached data
+    pub fn load(&self, username: &str) -> Result<Option<DashboardData>, String> {
+        let content = match std::fs::read_to_string(self.path(username)) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.to_string()),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Save data of the user, replacing cached data
+    pub fn save(&self, data: &DashboardData) -> Result<(), String> {
+        std::fs::create_dir_all(&self.directory).map_err(|err| err.to_string())?;
+        let content = serde_json::to_string(data).map_err(|err| err.to_string())?;
+        std::fs::write(self.path(&data.user.username), content).map_err(|err| err.to_string())
+    }
+}
+
+/// Fetch user, recently completed katas and authored katas
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * username (&str) - username of the user
+/// * katas (HashMap<String, CodeChallenge>) - details of already opened katas to keep
+pub async fn fetch_dashboard(
+    client: &dyn CodewarsApi,
+    username: &str,
+    katas: HashMap<String, CodeChallenge>,
+) -> Result<DashboardData, String> {
+    let user = client.get_user(username).await?;
+    let completed = client.get_completed_challenges(username, 0).await?.data;
+    let authored = client.get_authored_challenges(username).await?.data;
+    Ok(DashboardData {
+        fetched_at: Utc::now(),
+        user,
+        completed,
+        authored,
+        katas,
+    })
+}
+
+/// Get progress of the rank towards the next rank, from 0 to 1
+///
+/// # Examples
+/// ```
+/// use codewars_api::dashboard::rank_progress;
+/// use codewars_api::rest_api::models::{Color, Rank};
+///
+/// let rank = Rank {
+///     rank: -8,
+///     name: "8 kyu".to_string(),
+///     color: Color::White,
+///     score: 10,
+/// };
+/// assert_eq!(rank_progress(&rank), 0.5);
+/// ```
+pub fn rank_progress(rank: &Rank) -> f64 {
+    let Some(index) = RANK_SCORES
+        .iter()
+        .position(|(value, _)| *value == rank.rank)
+    else {
+        return 1.0;
+    };
+    let Some((_, next)) = RANK_SCORES.get(index + 1) else {
+        return 1.0;
+    };
+    let current = RANK_SCORES[index].1;
+    (rank.score.saturating_sub(current) as f64 / (next - current) as f64).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for dashboard data
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    /// Test fetching data and saving it to cache
+    #[tokio::test]
+    async fn test_fetch_and_cache() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        for (path, body) in [
+            ("/api/v1/users/some_user", fixtures::USER),
+            (
+                "/api/v1/users/some_user/code-challenges/completed?page=0",
+                fixtures::COMPLETED,
+            ),
+            (
+                "/api/v1/users/some_user/code-challenges/authored",
+                fixtures::AUTHORED,
+            ),
+        ] {
+            server
+                .mock("GET", path)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+        let data = fetch_dashboard(&client, "some_user", HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(data.completed.len(), 1);
+        assert_eq!(data.authored.len(), 2);
+
+        let directory = tempfile::tempdir().unwrap();
+        let cache = DashboardCache::new(directory.path().join("cache"));
+        assert_eq!(cache.load("some_user"), Ok(None));
+        cache.save(&data).unwrap();
+        assert_eq!(cache.load("some_user"), Ok(Some(data)));
+    }
+
+    /// Test progress towards the next rank
+    #[test]
+    fn test_rank_progress() {
+        let user = fixtures::user();
+        // 3 kyu with score 2116, 2 kyu starts at 4829
+        let progress = rank_progress(&user.ranks.overall);
+        assert!((progress - 348.0 / 3061.0).abs() < 1e-9);
+        let mut rank = user.ranks.overall;
+        rank.rank = 8;
+        assert_eq!(rank_progress(&rank), 1.0);
+    }
+}
@@ -0,0 +1,8 @@
+//! Processing of raw Codewars Markdown in kata descriptions
+//!
+//! Descriptions contain `~~~if:python` and `~~~if-not:javascript` conditional blocks and
+//! `%%%` groups of code fences, one per language. See [`render`] to render a description
+//! for a single language.
+
+pub mod extract;
+pub mod render;
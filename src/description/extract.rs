@@ -0,0 +1,296 @@
+//! Extraction of structured data from descriptions
+//!
+//! Extracts code blocks, headings, links and examples written like `f(1) => 2`, e.g. to
+//! pre-populate local test files.
+
+use crate::description::render::render_markdown;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// Separators between call and expected result of an example, longest first
+const EXAMPLE_SEPARATORS: [&str; 3] = ["-->", "==>", "=>"];
+
+/// Represents a heading of a description
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Heading {
+    /// Level of the heading, from 1 to 6
+    pub level: u8,
+    /// Text of the heading
+    pub text: String,
+}
+
+/// Represents a code block of a description
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CodeBlock {
+    /// Language from the info string of the fence, if any
+    pub language: Option<String>,
+    /// Code of the block
+    pub code: String,
+}
+
+/// Represents an example like `f(1) => 2`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Example {
+    /// Name of the called function, e.g. `f`
+    pub function: String,
+    /// Arguments of the call without parentheses, e.g. `1`
+    pub arguments: String,
+    /// Expected result, e.g. `2`
+    pub expected: String,
+}
+
+/// Represents a link of a description
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Link {
+    /// Text of the link
+    pub text: String,
+    /// URL of the link
+    pub url: String,
+}
+
+/// Represents structured data of a description
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct Document {
+    /// Headings in order of appearance
+    pub headings: Vec<Heading>,
+    /// Fenced and indented code blocks in order of appearance
+    pub code_blocks: Vec<CodeBlock>,
+    /// Examples found in inline code, text and code blocks without language
+    pub examples: Vec<Example>,
+    /// Links in order of appearance
+    pub links: Vec<Link>,
+}
+
+/// Implementation of Document
+impl Document {
+    /// Extract structured data from Markdown
+    ///
+    /// Conditional blocks are not processed, use [`Document::for_language`] for raw descriptions
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::description::extract::Document;
+    ///
+    /// let document = Document::parse("# Examples\n\n`add(1, 2) => 3`\n");
+    /// assert_eq!(document.headings[0].text, "Examples");
+    /// assert_eq!(document.examples[0].function, "add");
+    /// assert_eq!(document.examples[0].arguments, "1, 2");
+    /// assert_eq!(document.examples[0].expected, "3");
+    /// ```
+    pub fn parse(markdown: &str) -> Self {
+        let mut document = Self::default();
+        // Text of the open heading, code block or link
+        let mut heading: Option<(u8, String)> = None;
+        let mut code_block: Option<CodeBlock> = None;
+        let mut link: Option<Link> = None;
+        // Text of the open paragraph since the last inline code, split into events by parser
+        let mut paragraph = String::new();
+        for event in Parser::new(markdown) {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((heading_level(level), String::new()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    flush_examples(&mut document.examples, &mut paragraph);
+                    if let Some((level, text)) = heading.take() {
+                        document.headings.push(Heading {
+                            level,
+                            text: text.trim().to_string(),
+                        });
+                    }
+                }
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    let language = match kind {
+                        CodeBlockKind::Fenced(info) => info
+                            .split_whitespace()
+                            .next()
+                            .map(|language| language.to_string()),
+                        CodeBlockKind::Indented => None,
+                    };
+                    code_block = Some(CodeBlock {
+                        language,
+                        code: String::new(),
+                    });
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    if let Some(block) = code_block.take() {
+                        // Code of other languages contains arrows which are not examples
+                        if block.language.is_none() {
+                            document
+                                .examples
+                                .extend(block.code.lines().filter_map(parse_example));
+                        }
+                        document.code_blocks.push(block);
+                    }
+                }
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    link = Some(Link {
+                        text: String::new(),
+                        url: dest_url.to_string(),
+                    });
+                }
+                Event::End(TagEnd::Link) => {
+                    if let Some(link) = link.take() {
+                        document.links.push(link);
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(block) = &mut code_block {
+                        block.code.push_str(&text);
+                        continue;
+                    }
+                    push_inline(&mut heading, &mut link, &text);
+                    paragraph.push_str(&text);
+                }
+                Event::Code(code) => {
+                    push_inline(&mut heading, &mut link, &code);
+                    flush_examples(&mut document.examples, &mut paragraph);
+                    document.examples.extend(parse_example(&code));
+                }
+                Event::SoftBreak | Event::HardBreak => paragraph.push('\n'),
+                Event::End(TagEnd::Paragraph | TagEnd::Item | TagEnd::TableCell) => {
+                    flush_examples(&mut document.examples, &mut paragraph);
+                }
+                _ => {}
+            }
+        }
+        document
+    }
+
+    /// Extract structured data from raw description rendered for the language
+    ///
+    /// # Arguments:
+    /// * description (&str) - raw Codewars Markdown, e.g. `CodeChallenge.description`
+    /// * language (&str) - language to render description for, e.g. `python`
+    pub fn for_language(description: &str, language: &str) -> Self {
+        Self::parse(&render_markdown(description, language))
+    }
+}
+
+// Append inline text to the open heading and link
+fn push_inline(heading: &mut Option<(u8, String)>, link: &mut Option<Link>, text: &str) {
+    if let Some((_, heading)) = heading {
+        heading.push_str(text);
+    }
+    if let Some(link) = link {
+        link.text.push_str(text);
+    }
+}
+
+// Parse examples in every line of the text and clear it
+fn flush_examples(examples: &mut Vec<Example>, text: &mut String) {
+    examples.extend(text.lines().filter_map(parse_example));
+    text.clear();
+}
+
+// Convert heading level to number
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+// Parse example like `f(1) => 2`, text before the called function is ignored
+fn parse_example(line: &str) -> Option<Example> {
+    let line = line.trim().trim_end_matches([';', ',']);
+    let (call, expected) = EXAMPLE_SEPARATORS
+        .iter()
+        .find_map(|separator| line.split_once(separator))?;
+    let (call, expected) = (call.trim(), expected.trim());
+    let (before, arguments) = call.strip_suffix(')')?.split_once('(')?;
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+    let function = &before[before.trim_end_matches(is_identifier).len()..];
+    if function.is_empty() || expected.is_empty() {
+        return None;
+    }
+    Some(Example {
+        function: function.to_string(),
+        arguments: arguments.trim().to_string(),
+        expected: expected.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for extraction from descriptions
+
+    use super::*;
+
+    /// Test extracting all kinds of data
+    #[test]
+    fn test_extract() {
+        let description = "\
+# Sum of `numbers`
+
+See [the wiki](https://en.wikipedia.org/wiki/Summation) and <https://www.codewars.com>.
+
+## Examples
+
+`sum([1, 2]) => 3` and sum([]) --> 0
+
+```
+sum([5]) ==> 5;
+not an example
+```
+
+~~~if:javascript
+```javascript
+const f = (x) => x;
+```
+~~~
+~~~if:python
+```python
+def sum(numbers): pass
+```
+~~~
+";
+        let document = Document::for_language(description, "javascript");
+        assert_eq!(
+            document.headings,
+            vec![
+                Heading {
+                    level: 1,
+                    text: "Sum of numbers".to_string()
+                },
+                Heading {
+                    level: 2,
+                    text: "Examples".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            document.links,
+            vec![
+                Link {
+                    text: "the wiki".to_string(),
+                    url: "https://en.wikipedia.org/wiki/Summation".to_string()
+                },
+                Link {
+                    text: "https://www.codewars.com".to_string(),
+                    url: "https://www.codewars.com".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            document
+                .examples
+                .iter()
+                .map(|example| (example.arguments.as_str(), example.expected.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("[1, 2]", "3"), ("[]", "0"), ("[5]", "5")]
+        );
+        assert_eq!(document.code_blocks.len(), 2);
+        assert_eq!(
+            document.code_blocks[1],
+            CodeBlock {
+                language: Some("javascript".to_string()),
+                code: "const f = (x) => x;\n".to_string()
+            }
+        );
+    }
+}
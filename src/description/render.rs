@@ -0,0 +1,476 @@
+//! Rendering of descriptions for a chosen language
+//!
+//! Conditional blocks look like this and can be nested:
+//! ````markdown
+//! ~~~if:python,ruby
+//! Shown only for Python and Ruby
+//! ~~~
+//! ~~~if-not:javascript
+//! Shown for every language except JavaScript
+//! ~~~
+//! ````
+//! Code fences between two `%%%` lines are tabs of one snippet. Only fences of the chosen
+//! language are kept, or all of them if there is no such fence.
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// Formatting tags kept in raw HTML, which can't run scripts or load anything
+const ALLOWED_TAGS: &str = "b blockquote br code dd del div dl dt em hr i kbd li ol p pre s span \
+                            strong sub sup table tbody td tfoot th thead tr u ul";
+
+/// Represents a condition of a conditional block
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Condition {
+    // `~~~if:` block, shown only for listed languages
+    If(Vec<String>),
+    // `~~~if-not:` block, shown for all languages except listed ones
+    IfNot(Vec<String>),
+}
+
+/// Implementation of Condition
+impl Condition {
+    // Parse opening line of conditional block
+    fn parse(line: &str) -> Option<Self> {
+        let languages = |list: &str| {
+            list.split(',')
+                .map(|language| language.trim().to_lowercase())
+                .filter(|language| !language.is_empty())
+                .collect()
+        };
+        if let Some(list) = line.strip_prefix("~~~if-not:") {
+            Some(Self::IfNot(languages(list)))
+        } else {
+            line.strip_prefix("~~~if:")
+                .map(|list| Self::If(languages(list)))
+        }
+    }
+
+    // Check if block is shown for the language
+    fn matches(&self, language: &str) -> bool {
+        match self {
+            Self::If(languages) => languages.iter().any(|item| item == language),
+            Self::IfNot(languages) => !languages.iter().any(|item| item == language),
+        }
+    }
+}
+
+/// Represents a code fence in a `%%%` group
+#[derive(Debug, Default, Clone)]
+struct Tab {
+    // Language from the info string of the fence
+    language: String,
+    // Lines of the fence, including opening and closing lines
+    lines: Vec<String>,
+}
+
+/// Render description for the language to clean Markdown
+///
+/// Conditional blocks of other languages are removed, markers of conditional blocks and
+/// `%%%` groups are removed, and repeated blank lines outside of code fences are collapsed
+///
+/// # Arguments:
+/// * description (&str) - raw Codewars Markdown, e.g. `CodeChallenge.description`
+/// * language (&str) - language to render description for, e.g. `python`
+///
+/// # Examples
+/// ```
+/// use codewars_api::description::render::render_markdown;
+///
+/// let description = "Return the sum.\n\n\
+///     ~~~if:python\n\
+///     Use `sum` in Python.\n\
+///     ~~~\n\
+///     ~~~if-not:python\n\
+///     Don't use Python hints.\n\
+///     ~~~\n";
+/// assert_eq!(
+///     render_markdown(description, "python"),
+///     "Return the sum.\n\nUse `sum` in Python.\n"
+/// );
+/// ```
+pub fn render_markdown(description: &str, language: &str) -> String {
+    let language = language.to_lowercase();
+    // Whether each open conditional block is shown
+    let mut conditions: Vec<bool> = Vec::new();
+    // Marker of the open code fence, e.g. "```"
+    let mut fence: Option<String> = None;
+    // Tabs of the open `%%%` group
+    let mut group: Option<Vec<Tab>> = None;
+    let mut lines: Vec<String> = Vec::new();
+    for line in description.lines() {
+        let trimmed = line.trim();
+        let visible = conditions.iter().all(|shown| *shown);
+        if fence.is_none() {
+            if let Some(condition) = Condition::parse(trimmed) {
+                conditions.push(condition.matches(&language));
+                continue;
+            }
+            if trimmed == "~~~" && !conditions.is_empty() {
+                conditions.pop();
+                continue;
+            }
+            if trimmed == "%%%" {
+                match group.take() {
+                    Some(tabs) if visible => lines.extend(select_tabs(tabs, &language)),
+                    Some(_) => {}
+                    None => group = Some(Vec::new()),
+                }
+                continue;
+            }
+        }
+        // Track code fences, so markers inside code are not processed
+        let is_fence_line = match &fence {
+            Some(marker) => {
+                if closes_fence(trimmed, marker) {
+                    fence = None;
+                }
+                true
+            }
+            None => match opening_fence(trimmed) {
+                Some((marker, info)) => {
+                    fence = Some(marker);
+                    if let Some(tabs) = &mut group {
+                        tabs.push(Tab {
+                            language: info.split_whitespace().next().unwrap_or("").to_lowercase(),
+                            lines: Vec::new(),
+                        });
+                    }
+                    true
+                }
+                None => false,
+            },
+        };
+        if !visible {
+            continue;
+        }
+        match (&mut group, is_fence_line) {
+            (Some(tabs), true) => tabs.last_mut().unwrap().lines.push(line.to_string()),
+            // Text between fences of the group is not part of any tab
+            (Some(_), false) => {}
+            (None, _) => lines.push(line.to_string()),
+        }
+    }
+    // Keep tabs of unclosed group
+    if let Some(tabs) = group {
+        lines.extend(select_tabs(tabs, &language));
+    }
+    collapse_blank_lines(&lines)
+}
+
+/// Render description for the language to HTML
+///
+/// Description is rendered with [`render_markdown`] first. Tables and strikethrough are
+/// supported
+///
+/// Descriptions are written by users, so only formatting tags like `<pre>`, `<table>`, `<sup>`
+/// or `<br>` are kept from raw HTML, without their attributes. Other raw HTML is escaped and
+/// shown as text, and links and images with URLs other than `http`, `https`, `mailto` or
+/// relative ones get empty URLs. Output is not sanitized otherwise
+///
+/// # Examples
+/// ```
+/// use codewars_api::description::render::render_html;
+///
+/// let description = "# Sum\n\n~~~if:ruby\nUse `inject`.\n~~~\n";
+/// assert_eq!(render_html(description, "python"), "<h1>Sum</h1>\n");
+/// assert_eq!(
+///     render_html("Hi <script>alert(1)</script>", "python"),
+///     "<p>Hi &lt;script&gt;alert(1)&lt;/script&gt;</p>\n"
+/// );
+/// assert_eq!(render_html("x<sup>2</sup>", "python"), "<p>x<sup>2</sup></p>\n");
+/// ```
+pub fn render_html(description: &str, language: &str) -> String {
+    let markdown = render_markdown(description, language);
+    let parser = Parser::new_ext(
+        &markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    )
+    .map(|event| match event {
+        Event::Html(html) => Event::Html(sanitize_html(&html).into()),
+        Event::InlineHtml(html) => Event::InlineHtml(sanitize_html(&html).into()),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+// Keep allowed tags without their attributes and escape the rest of raw HTML
+fn sanitize_html(html: &str) -> String {
+    let mut output = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        escape_html(&mut output, &rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        match allowed_tag(&rest[1..end]) {
+            Some(tag) => output.push_str(&tag),
+            None => escape_html(&mut output, &rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    escape_html(&mut output, rest);
+    output
+}
+
+// Get tag without attributes if it is allowed, from the content between `<` and `>`
+fn allowed_tag(content: &str) -> Option<String> {
+    let (closing, content) = match content.strip_prefix('/') {
+        Some(content) => (true, content),
+        None => (false, content),
+    };
+    let name_length = content
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(content.len());
+    let (name, attributes) = content.split_at(name_length);
+    let name = name.to_ascii_lowercase();
+    let separated = attributes.is_empty() || attributes.starts_with([' ', '\t', '\n', '/']);
+    if !separated || !ALLOWED_TAGS.split(' ').any(|tag| tag == name) {
+        return None;
+    }
+    Some(match (closing, name == "br" || name == "hr") {
+        (true, _) => format!("</{}>", name),
+        (false, true) => format!("<{} />", name),
+        (false, false) => format!("<{}>", name),
+    })
+}
+
+// Escape text for HTML, keeping character references like `&lt;`
+fn escape_html(output: &mut String, text: &str) {
+    for (index, c) in text.char_indices() {
+        match c {
+            '&' if !is_reference(&text[index + 1..]) => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+}
+
+// Check if the text after `&` is the rest of a character reference
+fn is_reference(text: &str) -> bool {
+    let name = text.strip_prefix('#').unwrap_or(text);
+    let length = name
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(name.len());
+    length > 0 && name[length..].starts_with(';')
+}
+
+// Replace URL with empty one if its scheme can run scripts, e.g. `javascript:`
+fn safe_url(url: CowStr) -> CowStr {
+    let scheme = url
+        .split(['/', '?', '#'])
+        .next()
+        .and_then(|start| start.split_once(':'))
+        .map(|(scheme, _)| scheme.trim().to_lowercase());
+    match scheme.as_deref() {
+        None | Some("http" | "https" | "mailto") => url,
+        Some(_) => CowStr::Borrowed(""),
+    }
+}
+
+// Get marker and info string of the code fence opened by the line
+fn opening_fence(line: &str) -> Option<(String, &str)> {
+    let character = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = line.chars().take_while(|c| *c == character).count();
+    if length < 3 {
+        return None;
+    }
+    Some((character.to_string().repeat(length), line[length..].trim()))
+}
+
+// Check if the line closes the code fence with the marker
+fn closes_fence(line: &str, marker: &str) -> bool {
+    let character = marker.chars().next().unwrap();
+    line.len() >= marker.len() && line.chars().all(|c| c == character)
+}
+
+// Get lines of tabs of the language, or of all tabs if there are none
+fn select_tabs(tabs: Vec<Tab>, language: &str) -> Vec<String> {
+    let has_language = tabs.iter().any(|tab| tab.language == language);
+    let mut lines = Vec::new();
+    for tab in tabs {
+        if has_language && tab.language != language {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(tab.lines);
+    }
+    lines
+}
+
+// Join lines, removing leading, trailing and repeated blank lines and trailing whitespace
+//
+// Lines of code fences are kept as they are. Two trailing spaces are kept, because they are
+// a hard line break in Markdown
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut output = String::new();
+    let mut blank = false;
+    // Marker of the open code fence
+    let mut fence: Option<String> = None;
+    for line in lines {
+        let trimmed = line.trim();
+        if let Some(marker) = &fence {
+            if closes_fence(trimmed, marker) {
+                fence = None;
+            }
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+        if trimmed.is_empty() {
+            blank = !output.is_empty();
+            continue;
+        }
+        if blank {
+            output.push('\n');
+            blank = false;
+        }
+        fence = opening_fence(trimmed).map(|(marker, _)| marker);
+        output.push_str(line.trim_end());
+        if fence.is_none() && line.ends_with("  ") {
+            output.push_str("  ");
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for rendering of descriptions
+
+    use super::*;
+
+    const DESCRIPTION: &str = "\
+# Title
+
+Write a function.
+
+~~~if:python,ruby
+Dynamic languages note.
+
+~~~if-not:ruby
+Python only.
+~~~
+~~~
+
+~~~if-not:python
+```
+~~~if:python
+not a conditional
+~~~
+```
+~~~
+
+%%%
+```python
+def f(x): pass
+```
+
+```javascript
+function f(x) {}
+```
+%%%
+";
+
+    /// Test rendering Markdown for different languages
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            render_markdown(DESCRIPTION, "Python"),
+            "# Title\n\nWrite a function.\n\nDynamic languages note.\n\nPython only.\n\n\
+             ```python\ndef f(x): pass\n```\n"
+        );
+        assert_eq!(
+            render_markdown(DESCRIPTION, "ruby"),
+            "# Title\n\nWrite a function.\n\nDynamic languages note.\n\n\
+             ```\n~~~if:python\nnot a conditional\n~~~\n```\n\n\
+             ```python\ndef f(x): pass\n```\n\n```javascript\nfunction f(x) {}\n```\n"
+        );
+    }
+
+    /// Test rendering HTML
+    #[test]
+    fn test_render_html() {
+        assert_eq!(
+            render_html(DESCRIPTION, "javascript"),
+            "<h1>Title</h1>\n<p>Write a function.</p>\n\
+             <pre><code>~~~if:python\nnot a conditional\n~~~\n</code></pre>\n\
+             <pre><code class=\"language-javascript\">function f(x) {}\n</code></pre>\n"
+        );
+    }
+
+    /// Test that blank lines and whitespace in code fences are kept
+    #[test]
+    fn test_keep_code_fences() {
+        let description = "Example:\n\n\n```python\ndef f(x):  \n\n\n    return x\n```\n\n\nEnd.\n";
+        assert_eq!(
+            render_markdown(description, "python"),
+            "Example:\n\n```python\ndef f(x):  \n\n\n    return x\n```\n\nEnd.\n"
+        );
+    }
+
+    /// Test that hard line breaks are kept
+    #[test]
+    fn test_keep_hard_line_breaks() {
+        let description = "First line  \nSecond line   \nThird line \n";
+        assert_eq!(
+            render_markdown(description, "python"),
+            "First line  \nSecond line  \nThird line\n"
+        );
+        assert_eq!(
+            render_html(description, "python"),
+            "<p>First line<br />\nSecond line<br />\nThird line</p>\n"
+        );
+    }
+
+    /// Test keeping formatting tags, escaping other raw HTML and removing script URLs
+    #[test]
+    fn test_html_is_escaped() {
+        let description = "<div onclick=\"alert(1)\">Hi</div>\n\n\
+            <script>alert(1)</script>\n\n\
+            <pre>a &lt; b</pre>\n\n\
+            <table><tr><TD colspan=\"2\">1</td></tr></table>\n\n\
+            2<sup>10</sup> <img src=x onerror=alert(1)> <br> <!-- note --> <sup\n\n\
+            [link](javascript:alert(1)) [site](https://www.codewars.com) ![image](data:text/html,x)\n";
+        assert_eq!(
+            render_html(description, "python"),
+            "<div>Hi</div>\n\
+             &lt;script&gt;alert(1)&lt;/script&gt;\n\
+             <pre>a &lt; b</pre>\n\
+             <table><tr><td>1</td></tr></table>\n\
+             <p>2<sup>10</sup> &lt;img src=x onerror=alert(1)&gt; <br /> \
+             &lt;!-- note --&gt; &lt;sup</p>\n\
+             <p><a href=\"\">link</a> <a href=\"https://www.codewars.com\">site</a> \
+             <img src=\"\" alt=\"image\" /></p>\n"
+        );
+    }
+}
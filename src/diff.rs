@@ -0,0 +1,323 @@
+//! Structured difference between two snapshots of a user
+//!
+//! Codewars API returns only current state of a user, so [`UserDiff`] compares two states
+//! fetched at different times, e.g. for weekly reports.
+
+use crate::rest_api::models::{Rank, User};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Represents a change of a number
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone, Copy)]
+pub struct Delta {
+    /// Value in the old snapshot
+    pub before: u64,
+    /// Value in the new snapshot
+    pub after: u64,
+    /// Difference between new and old values
+    pub delta: i64,
+}
+
+/// Implementation of Delta
+impl Delta {
+    /// Create new delta between two values
+    pub fn new(before: u64, after: u64) -> Self {
+        Self {
+            before,
+            after,
+            delta: after as i64 - before as i64,
+        }
+    }
+
+    /// Check if value has changed
+    pub fn is_changed(&self) -> bool {
+        self.delta != 0
+    }
+}
+
+/// Represents a change of a rank
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct RankChange {
+    /// Rank in the old snapshot
+    pub before: Rank,
+    /// Rank in the new snapshot
+    pub after: Rank,
+    /// Change of the score
+    pub score: Delta,
+}
+
+/// Implementation of RankChange
+impl RankChange {
+    /// Create new change between two ranks
+    pub fn new(before: &Rank, after: &Rank) -> Self {
+        Self {
+            before: before.clone(),
+            after: after.clone(),
+            score: Delta::new(before.score, after.score),
+        }
+    }
+
+    /// Check if rank itself has changed, not only the score
+    pub fn is_rank_changed(&self) -> bool {
+        self.before.rank != self.after.rank
+    }
+}
+
+/// Represents a change of a clan
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct ClanChange {
+    /// Clan in the old snapshot
+    pub before: String,
+    /// Clan in the new snapshot
+    pub after: String,
+}
+
+/// Represents a difference between two snapshots of a user
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UserDiff {
+    /// Username of the user in the new snapshot
+    pub username: String,
+    /// Change of honor
+    pub honor: Delta,
+    /// Change of position on the overall leaderboard. Negative delta means moving up
+    pub leaderboard_position: Delta,
+    /// Change of overall rank, if rank or score has changed
+    pub overall_rank: Option<RankChange>,
+    /// Changes of ranks in languages present in both snapshots, if rank or score has changed
+    pub languages: BTreeMap<String, RankChange>,
+    /// Ranks in languages which are present only in the new snapshot
+    pub new_languages: BTreeMap<String, Rank>,
+    /// Ranks in languages which are present only in the old snapshot
+    pub removed_languages: BTreeMap<String, Rank>,
+    /// Skills which are present only in the new snapshot
+    pub added_skills: Vec<String>,
+    /// Skills which are present only in the old snapshot
+    pub removed_skills: Vec<String>,
+    /// Change of clan, if it has changed
+    pub clan: Option<ClanChange>,
+    /// Change of number of authored katas
+    pub total_authored: Delta,
+    /// Change of number of completed katas
+    pub total_completed: Delta,
+}
+
+/// Implementation of UserDiff
+impl UserDiff {
+    /// Compute difference between two snapshots of a user
+    ///
+    /// # Arguments:
+    /// * old (&User) - older snapshot
+    /// * new (&User) - newer snapshot
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use codewars_api::rest_api::client::RestCodewarsClient;
+    /// use codewars_api::diff::UserDiff;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let client = RestCodewarsClient::new();
+    /// let old = client.get_user("ANKDDEV").await.unwrap();
+    /// // Some time later
+    /// let new = client.get_user("ANKDDEV").await.unwrap();
+    /// let diff = UserDiff::new(&old, &new);
+    /// println!("{}", diff);
+    /// println!("{}", serde_json::to_string(&diff).unwrap());
+    /// # }
+    /// ```
+    pub fn new(old: &User, new: &User) -> Self {
+        let overall_rank = RankChange::new(&old.ranks.overall, &new.ranks.overall);
+        let mut languages = BTreeMap::new();
+        let mut new_languages = BTreeMap::new();
+        for (language, rank) in &new.ranks.languages {
+            match old.ranks.languages.get(language) {
+                Some(rank_was) if rank_was != rank => {
+                    languages.insert(language.clone(), RankChange::new(rank_was, rank));
+                }
+                Some(_) => {}
+                None => {
+                    new_languages.insert(language.clone(), rank.clone());
+                }
+            }
+        }
+        let removed_languages = old
+            .ranks
+            .languages
+            .iter()
+            .filter(|(language, _)| !new.ranks.languages.contains_key(*language))
+            .map(|(language, rank)| (language.clone(), rank.clone()))
+            .collect();
+        let old_skills: BTreeSet<&String> = old.skills.iter().collect();
+        let new_skills: BTreeSet<&String> = new.skills.iter().collect();
+        Self {
+            username: new.username.clone(),
+            honor: Delta::new(old.honor, new.honor),
+            leaderboard_position: Delta::new(old.leaderboard_position, new.leaderboard_position),
+            overall_rank: (old.ranks.overall != new.ranks.overall).then_some(overall_rank),
+            languages,
+            new_languages,
+            removed_languages,
+            added_skills: new_skills
+                .difference(&old_skills)
+                .map(|skill| skill.to_string())
+                .collect(),
+            removed_skills: old_skills
+                .difference(&new_skills)
+                .map(|skill| skill.to_string())
+                .collect(),
+            clan: (old.clan != new.clan).then(|| ClanChange {
+                before: old.clan.clone(),
+                after: new.clan.clone(),
+            }),
+            total_authored: Delta::new(
+                old.code_challenges.total_authored,
+                new.code_challenges.total_authored,
+            ),
+            total_completed: Delta::new(
+                old.code_challenges.total_completed,
+                new.code_challenges.total_completed,
+            ),
+        }
+    }
+
+    /// Check if nothing has changed between snapshots
+    pub fn is_empty(&self) -> bool {
+        !self.honor.is_changed()
+            && !self.leaderboard_position.is_changed()
+            && self.overall_rank.is_none()
+            && self.languages.is_empty()
+            && self.new_languages.is_empty()
+            && self.removed_languages.is_empty()
+            && self.added_skills.is_empty()
+            && self.removed_skills.is_empty()
+            && self.clan.is_none()
+            && !self.total_authored.is_changed()
+            && !self.total_completed.is_changed()
+    }
+}
+
+/// Format delta like `+10 (544 -> 554)`
+impl fmt::Display for Delta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+} ({} -> {})", self.delta, self.before, self.after)
+    }
+}
+
+/// Format rank change like `3 kyu -> 2 kyu, score +120 (2116 -> 2236)`
+impl fmt::Display for RankChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_rank_changed() {
+            write!(f, "{} -> {}, ", self.before.name, self.after.name)?;
+        } else {
+            write!(f, "{}, ", self.after.name)?;
+        }
+        write!(f, "score {}", self.score)
+    }
+}
+
+/// Format difference as human-readable text, one change per line
+impl fmt::Display for UserDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.username)?;
+        if self.is_empty() {
+            return writeln!(f, "  no changes");
+        }
+        if self.honor.is_changed() {
+            writeln!(f, "  honor: {}", self.honor)?;
+        }
+        if self.leaderboard_position.is_changed() {
+            writeln!(f, "  leaderboard position: {}", self.leaderboard_position)?;
+        }
+        if let Some(rank) = &self.overall_rank {
+            writeln!(f, "  overall rank: {}", rank)?;
+        }
+        for (language, rank) in &self.languages {
+            writeln!(f, "  {}: {}", language, rank)?;
+        }
+        for (language, rank) in &self.new_languages {
+            writeln!(f, "  new language {}: {}", language, rank.name)?;
+        }
+        for (language, rank) in &self.removed_languages {
+            writeln!(f, "  removed language {}: {}", language, rank.name)?;
+        }
+        if !self.added_skills.is_empty() {
+            writeln!(f, "  added skills: {}", self.added_skills.join(", "))?;
+        }
+        if !self.removed_skills.is_empty() {
+            writeln!(f, "  removed skills: {}", self.removed_skills.join(", "))?;
+        }
+        if let Some(clan) = &self.clan {
+            writeln!(f, "  clan: '{}' -> '{}'", clan.before, clan.after)?;
+        }
+        if self.total_authored.is_changed() {
+            writeln!(f, "  authored katas: {}", self.total_authored)?;
+        }
+        if self.total_completed.is_changed() {
+            writeln!(f, "  completed katas: {}", self.total_completed)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for user diff
+
+    use super::*;
+    use crate::fixtures::user;
+
+    /// Test that diff of the same user is empty
+    #[test]
+    fn test_same_user() {
+        let diff = UserDiff::new(&user(), &user());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "some_user:\n  no changes\n");
+    }
+
+    /// Test diff of changed user
+    #[test]
+    fn test_changed_user() {
+        let old = user();
+        let mut new = user();
+        new.honor += 10;
+        new.leaderboard_position -= 4;
+        new.clan = "other clan".to_string();
+        new.skills.retain(|skill| skill != "rails");
+        new.skills.push("rust".to_string());
+        new.ranks.languages.get_mut("ruby").unwrap().score += 15;
+        let javascript = new.ranks.languages.remove("javascript").unwrap();
+        new.ranks.languages.insert("rust".to_string(), javascript);
+        new.code_challenges.total_completed += 2;
+
+        let diff = UserDiff::new(&old, &new);
+        assert_eq!(diff.honor.delta, 10);
+        assert_eq!(diff.leaderboard_position.delta, -4);
+        assert_eq!(diff.overall_rank, None);
+        assert_eq!(diff.languages.keys().collect::<Vec<_>>(), vec!["ruby"]);
+        assert!(!diff.languages["ruby"].is_rank_changed());
+        assert_eq!(diff.new_languages.keys().collect::<Vec<_>>(), vec!["rust"]);
+        assert_eq!(
+            diff.removed_languages.keys().collect::<Vec<_>>(),
+            vec!["javascript"]
+        );
+        assert_eq!(diff.added_skills, vec!["rust"]);
+        assert_eq!(diff.removed_skills, vec!["rails"]);
+        assert_eq!(
+            diff.to_string(),
+            "some_user:\n\
+             \x20 honor: +10 (544 -> 554)\n\
+             \x20 leaderboard position: -4 (134 -> 130)\n\
+             \x20 ruby: 4 kyu, score +15 (1005 -> 1020)\n\
+             \x20 new language rust: 3 kyu\n\
+             \x20 removed language javascript: 3 kyu\n\
+             \x20 added skills: rust\n\
+             \x20 removed skills: rails\n\
+             \x20 clan: 'some clan' -> 'other clan'\n\
+             \x20 completed katas: +2 (230 -> 232)\n"
+        );
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["honor"]["delta"], 10);
+        assert_eq!(json["clan"]["after"], "other clan");
+    }
+}
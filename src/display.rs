@@ -0,0 +1,394 @@
+//! Rendering of users and katas for terminals
+//!
+//! Ranks are colored with ANSI colors matching [`Color`] and content is laid out in Unicode
+//! boxes. Use [`DisplayOptions::detect`] to fall back to plain ASCII output without colors
+//! when standard output isn't a terminal.
+
+use crate::rest_api::models::{CodeChallenge, Color, CompletedChallenges, User};
+use std::io::IsTerminal;
+
+/// Default width of output when it can't be detected
+const DEFAULT_WIDTH: usize = 80;
+/// Minimal width of output
+const MIN_WIDTH: usize = 20;
+
+/// Options of terminal output
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DisplayOptions {
+    /// Color ranks with ANSI escape sequences
+    pub colors: bool,
+    /// Draw boxes with Unicode characters instead of ASCII
+    pub unicode: bool,
+    /// Width of output in columns
+    pub width: usize,
+}
+
+/// Implementation of DisplayOptions
+impl DisplayOptions {
+    /// Detect options for standard output
+    ///
+    /// Colors and Unicode are enabled only if standard output is a terminal. Colors are also
+    /// disabled if `NO_COLOR` environment variable is set. Width is read from `COLUMNS`
+    /// environment variable, defaulting to 80
+    pub fn detect() -> Self {
+        let terminal = std::io::stdout().is_terminal();
+        Self {
+            colors: terminal && std::env::var_os("NO_COLOR").is_none(),
+            unicode: terminal,
+            width: std::env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.parse().ok())
+                .unwrap_or(DEFAULT_WIDTH),
+        }
+    }
+
+    /// Options for plain ASCII output without colors
+    pub fn plain(width: usize) -> Self {
+        Self {
+            colors: false,
+            unicode: false,
+            width,
+        }
+    }
+}
+
+/// Characters used to draw boxes
+struct BoxChars {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+    left_separator: char,
+    right_separator: char,
+}
+
+/// Unicode box with rounded corners
+const UNICODE_BOX: BoxChars = BoxChars {
+    top_left: '╭',
+    top_right: '╮',
+    bottom_left: '╰',
+    bottom_right: '╯',
+    horizontal: '─',
+    vertical: '│',
+    left_separator: '├',
+    right_separator: '┤',
+};
+
+/// ASCII box for plain output
+const ASCII_BOX: BoxChars = BoxChars {
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    horizontal: '-',
+    vertical: '|',
+    left_separator: '+',
+    right_separator: '+',
+};
+
+/// Render user with overall and language ranks
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::display::{render_user, DisplayOptions};
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let user = client.get_user("ANKDDEV").await.unwrap();
+/// print!("{}", render_user(&user, &DisplayOptions::detect()));
+/// # }
+/// ```
+pub fn render_user(user: &User, options: &DisplayOptions) -> String {
+    let overall = &user.ranks.overall;
+    let mut fields = vec![
+        field("Name", &user.name),
+        field("Clan", &user.clan),
+        field("Honor", &user.honor.to_string()),
+        field("Position", &user.leaderboard_position.to_string()),
+        format!(
+            "Rank: {} (score {})",
+            rank_badge(&overall.name, &overall.color, options),
+            overall.score
+        ),
+        field(
+            "Katas",
+            &format!(
+                "{} completed, {} authored",
+                user.code_challenges.total_completed, user.code_challenges.total_authored
+            ),
+        ),
+    ];
+    if !user.skills.is_empty() {
+        fields.push(field("Skills", &user.skills.join(", ")));
+    }
+    let mut languages: Vec<_> = user.ranks.languages.iter().collect();
+    // Sort languages from the highest score
+    languages.sort_by(|a, b| b.1.score.cmp(&a.1.score).then(a.0.cmp(b.0)));
+    let width = languages
+        .iter()
+        .map(|(language, _)| language.chars().count())
+        .max()
+        .unwrap_or(0);
+    let languages = languages
+        .into_iter()
+        .map(|(language, rank)| {
+            format!(
+                "{:<width$}  {}  {}",
+                language,
+                rank_badge(&rank.name, &rank.color, options),
+                rank.score,
+                width = width
+            )
+        })
+        .collect();
+    draw_box(&user.username, vec![fields, languages], options)
+}
+
+/// Render kata with its description wrapped to the width
+pub fn render_kata(kata: &CodeChallenge, options: &DisplayOptions) -> String {
+    let rank = match &kata.rank {
+        Some(rank) => rank_badge(&rank.name, &rank.color, options),
+        None => "beta".to_string(),
+    };
+    let fields = vec![
+        format!("Rank: {}", rank),
+        field("Category", &kata.category),
+        field("Tags", &kata.tags.join(", ")),
+        field("Languages", &kata.languages.join(", ")),
+        field("Author", &kata.created_by.username),
+        field(
+            "Stats",
+            &format!(
+                "{} completed, {} stars, vote score {}",
+                kata.total_completed, kata.total_stars, kata.vote_score
+            ),
+        ),
+        field("URL", &kata.url),
+    ];
+    let description = kata.description.lines().map(str::to_string).collect();
+    draw_box(&kata.name, vec![fields, description], options)
+}
+
+/// Render page of completed katas
+pub fn render_completed(completed: &CompletedChallenges, options: &DisplayOptions) -> String {
+    let lines = completed
+        .data
+        .iter()
+        .map(|kata| {
+            let languages = kata.languages();
+            let date = kata.completed_at.get(..10).unwrap_or(&kata.completed_at);
+            format!("{}  {} ({})", date, kata.name, languages.join(", "))
+        })
+        .collect();
+    draw_box(
+        &format!("Completed katas ({} total)", completed.total_items),
+        vec![lines],
+        options,
+    )
+}
+
+// Format field as `name: value`
+fn field(name: &str, value: &str) -> String {
+    format!("{}: {}", name, value)
+}
+
+// Get ANSI escape sequence of the rank color
+fn ansi_color(color: &Color) -> &'static str {
+    match color {
+        Color::White => "\x1b[97m",
+        Color::Yellow => "\x1b[33m",
+        Color::Blue => "\x1b[34m",
+        Color::Purple => "\x1b[35m",
+        // Black text is invisible on dark terminals
+        Color::Black => "\x1b[90m",
+        Color::Red => "\x1b[31m",
+    }
+}
+
+// Render rank name, colored if colors are enabled
+fn rank_badge(name: &str, color: &Color, options: &DisplayOptions) -> String {
+    match options.colors {
+        true => format!("{}{}\x1b[0m", ansi_color(color), name),
+        false => name.to_string(),
+    }
+}
+
+// Get width of the text in columns, ignoring ANSI escape sequences
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut escape = false;
+    for c in text.chars() {
+        match (escape, c) {
+            (false, '\x1b') => escape = true,
+            (true, 'm') => escape = false,
+            (true, _) => {}
+            (false, _) => width += 1,
+        }
+    }
+    width
+}
+
+// Wrap the line by words to the width, breaking words longer than the width
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    if visible_width(line) <= width {
+        return vec![line.to_string()];
+    }
+    // Keep indentation of code and lists, leaving at least one column for the text
+    let indent: String = line
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .take(width.saturating_sub(1))
+        .collect();
+    let mut lines = Vec::new();
+    let mut current = indent.clone();
+    for word in line.split_whitespace() {
+        let mut word = word.to_string();
+        if visible_width(&current) > indent.len()
+            && visible_width(&current) + 1 + visible_width(&word) > width
+        {
+            lines.push(std::mem::replace(&mut current, indent.clone()));
+        }
+        // Break words which don't fit even on their own line
+        while visible_width(&current) + visible_width(&word) > width && !word.contains('\x1b') {
+            if word.is_empty() {
+                break;
+            }
+            let available = width.saturating_sub(visible_width(&current)).max(1);
+            let rest = word.split_off(
+                word.char_indices()
+                    .nth(available)
+                    .map_or(word.len(), |(i, _)| i),
+            );
+            current.push_str(&word);
+            lines.push(std::mem::replace(&mut current, indent.clone()));
+            word = rest;
+        }
+        if visible_width(&current) > indent.len() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if visible_width(&current) > indent.len() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+// Draw box with the title and sections separated by horizontal lines
+fn draw_box(title: &str, sections: Vec<Vec<String>>, options: &DisplayOptions) -> String {
+    let chars = match options.unicode {
+        true => &UNICODE_BOX,
+        false => &ASCII_BOX,
+    };
+    let width = options.width.max(MIN_WIDTH);
+    let inner = width - 4;
+    let horizontal = |length: usize| chars.horizontal.to_string().repeat(length);
+    let title: String = title.chars().take(inner - 2).collect();
+    let mut output = format!(
+        "{}{} {} {}{}\n",
+        chars.top_left,
+        chars.horizontal,
+        title,
+        horizontal(width - 5 - title.chars().count()),
+        chars.top_right
+    );
+    let sections: Vec<Vec<String>> = sections
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect();
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 {
+            output.push_str(&format!(
+                "{}{}{}\n",
+                chars.left_separator,
+                horizontal(width - 2),
+                chars.right_separator
+            ));
+        }
+        for line in section.iter().flat_map(|line| wrap(line, inner)) {
+            output.push_str(&format!(
+                "{} {}{} {}\n",
+                chars.vertical,
+                line,
+                " ".repeat(inner.saturating_sub(visible_width(&line))),
+                chars.vertical
+            ));
+        }
+    }
+    output.push_str(&format!(
+        "{}{}{}\n",
+        chars.bottom_left,
+        horizontal(width - 2),
+        chars.bottom_right
+    ));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for terminal rendering
+
+    use super::*;
+    use crate::fixtures;
+
+    /// Test plain rendering of user
+    #[test]
+    fn test_render_user_plain() {
+        let user = fixtures::user();
+        assert_eq!(
+            render_user(&user, &DisplayOptions::plain(40)),
+            "+- some_user --------------------------+\n\
+             | Name: Some Person                    |\n\
+             | Clan: some clan                      |\n\
+             | Honor: 544                           |\n\
+             | Position: 134                        |\n\
+             | Rank: 3 kyu (score 2116)             |\n\
+             | Katas: 230 completed, 3 authored     |\n\
+             | Skills: ruby, c#, .net, javascript,  |\n\
+             | coffeescript, nodejs, rails          |\n\
+             +--------------------------------------+\n\
+             | javascript    3 kyu  1819            |\n\
+             | ruby          4 kyu  1005            |\n\
+             | coffeescript  4 kyu  870             |\n\
+             +--------------------------------------+\n"
+        );
+    }
+
+    /// Test colored rendering of kata with wrapped description
+    #[test]
+    fn test_render_kata_colored() {
+        let kata = fixtures::kata();
+        let options = DisplayOptions {
+            colors: true,
+            unicode: true,
+            width: 30,
+        };
+        let output = render_kata(&kata, &options);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "╭─ Valid Braces ─────────────╮");
+        assert_eq!(lines[1], "│ Rank: \x1b[34m4 kyu\x1b[0m                │");
+        assert!(lines.contains(&"├────────────────────────────┤"));
+        assert_eq!(*lines.last().unwrap(), "╰────────────────────────────╯");
+        // Every line has the same visible width
+        assert!(lines.iter().all(|line| visible_width(line) == 30));
+    }
+
+    /// Test wrapping of long words and indented lines
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("abcdefgh ij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("  a b c", 5), vec!["  a b", "  c"]);
+        assert_eq!(wrap("", 5), vec![""]);
+        // Indentation wider than the line is cut to leave room for the text
+        let indent = " ".repeat(15);
+        let expected: Vec<String> = "abcdefghi"
+            .chars()
+            .map(|c| format!("{}{}", indent, c))
+            .collect();
+        assert_eq!(wrap(&format!("{}abcdef ghi", " ".repeat(20)), 16), expected);
+    }
+}
@@ -0,0 +1,316 @@
+//! Export of completed challenges to CSV, JSON Lines and Markdown
+//!
+//! Rows can be enriched with rank and tags of katas fetched with `get_kata`.
+
+use crate::rest_api::api::get_kata_if_exists;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::CompletedChallenge;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Format of exported file
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum ExportFormat {
+    /// Comma-separated values with header
+    Csv,
+    /// One JSON object per line
+    #[strum(serialize = "jsonl")]
+    JsonLines,
+    /// Markdown table
+    Markdown,
+}
+
+/// Represents a row of exported completion history
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct ExportRow {
+    /// ID of the kata
+    pub id: String,
+    /// Name of the kata
+    pub name: String,
+    /// Slug of the kata
+    pub slug: String,
+    /// Date and time of the completion
+    pub completed_at: String,
+    /// Sorted languages the kata was completed in, without duplicates
+    pub languages: Vec<String>,
+    /// Rank name of the kata, if rows were enriched and kata is approved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    /// Tags of the kata, if rows were enriched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Implementation of ExportRow
+impl ExportRow {
+    /// Create row from completed challenge
+    pub fn new(kata: &CompletedChallenge) -> Self {
+        Self {
+            id: kata.id.clone(),
+            name: kata.name.clone(),
+            slug: kata.slug.clone(),
+            completed_at: kata.completed_at.clone(),
+            languages: kata.languages(),
+            rank: None,
+            tags: None,
+        }
+    }
+}
+
+/// Fetch every kata with `get_kata` and add its rank and tags to the row
+///
+/// Katas which were deleted from Codewars are skipped
+///
+/// # Errors:
+/// See [`CodewarsApi::get_kata`]
+pub async fn enrich(client: &dyn CodewarsApi, rows: &mut [ExportRow]) -> Result<(), String> {
+    for row in rows {
+        if let Some(kata) = get_kata_if_exists(client, &row.id).await? {
+            row.rank = kata.rank.map(|rank| rank.name);
+            row.tags = Some(kata.tags);
+        }
+    }
+    Ok(())
+}
+
+/// Write rows in the format
+///
+/// Rank and tags columns are written only if at least one row was enriched
+///
+/// # Arguments:
+/// * rows (&[ExportRow]) - rows to write
+/// * format (ExportFormat) - format of the output
+/// * writer (impl Write) - where to write the output
+///
+/// # Examples
+/// ```
+/// use codewars_api::export::{write, ExportFormat, ExportRow};
+///
+/// let rows = vec![ExportRow {
+///     id: "514b92a657cdc65150000006".to_string(),
+///     name: "Multiples of 3 and 5".to_string(),
+///     slug: "multiples-of-3-and-5".to_string(),
+///     completed_at: "2017-04-06T16:32:09Z".to_string(),
+///     languages: vec!["javascript".to_string(), "ruby".to_string()],
+///     ..Default::default()
+/// }];
+/// let mut output = Vec::new();
+/// write(&rows, ExportFormat::Csv, &mut output).unwrap();
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "id,name,slug,completed_at,languages\n\
+///      514b92a657cdc65150000006,Multiples of 3 and 5,multiples-of-3-and-5,2017-04-06T16:32:09Z,\"javascript, ruby\"\n"
+/// );
+/// ```
+pub fn write(
+    rows: &[ExportRow],
+    format: ExportFormat,
+    mut writer: impl Write,
+) -> Result<(), String> {
+    let output = match format {
+        ExportFormat::Csv => to_csv(rows),
+        ExportFormat::JsonLines => to_json_lines(rows)?,
+        ExportFormat::Markdown => to_markdown(rows),
+    };
+    writer
+        .write_all(output.as_bytes())
+        .map_err(|err| err.to_string())
+}
+
+/// Fetch all completed challenges of the user and write them in the format
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * username (&str) - username of the user
+/// * format (ExportFormat) - format of the output
+/// * enrich_rows (bool) - whether to add rank and tags of katas with `get_kata`
+/// * writer (impl Write) - where to write the output
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::export::{export_completed, ExportFormat};
+/// use codewars_api::RestCodewarsClient;
+/// use std::fs::File;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let file = File::create("completed.csv").unwrap();
+/// export_completed(&client, "ANKDDEV", ExportFormat::Csv, true, file).await.unwrap();
+/// # }
+/// ```
+pub async fn export_completed(
+    client: &dyn CodewarsApi,
+    username: &str,
+    format: ExportFormat,
+    enrich_rows: bool,
+    writer: impl Write,
+) -> Result<(), String> {
+    let completed = client.get_all_completed_challenges(username).await?;
+    let mut rows: Vec<ExportRow> = completed.iter().map(ExportRow::new).collect();
+    if enrich_rows {
+        enrich(client, &mut rows).await?;
+    }
+    write(&rows, format, writer)
+}
+
+// Get header and cells of every row, adding rank and tags only if some row is enriched
+fn cells(rows: &[ExportRow]) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let enriched = rows.iter().any(|row| row.tags.is_some());
+    let mut header = vec!["id", "name", "slug", "completed_at", "languages"];
+    if enriched {
+        header.extend(["rank", "tags"]);
+    }
+    let cells = rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![
+                row.id.clone(),
+                row.name.clone(),
+                row.slug.clone(),
+                row.completed_at.clone(),
+                row.languages.join(", "),
+            ];
+            if enriched {
+                cells.push(row.rank.clone().unwrap_or_default());
+                cells.push(row.tags.clone().unwrap_or_default().join(", "));
+            }
+            cells
+        })
+        .collect();
+    (header, cells)
+}
+
+// Render rows as CSV, quoting cells as described in RFC 4180
+fn to_csv(rows: &[ExportRow]) -> String {
+    let quote = |cell: &str| {
+        if cell.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    };
+    let (header, cells) = cells(rows);
+    let mut output = header.join(",");
+    output.push('\n');
+    for row in cells {
+        output.push_str(
+            &row.iter()
+                .map(|cell| quote(cell))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        output.push('\n');
+    }
+    output
+}
+
+// Render rows as JSON Lines
+fn to_json_lines(rows: &[ExportRow]) -> Result<String, String> {
+    let mut output = String::new();
+    for row in rows {
+        output.push_str(&serde_json::to_string(row).map_err(|err| err.to_string())?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+// Render rows as Markdown table
+fn to_markdown(rows: &[ExportRow]) -> String {
+    let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', " ");
+    let (header, cells) = cells(rows);
+    let mut output = format!("| {} |\n", header.join(" | "));
+    output.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+    for row in cells {
+        output.push_str(&format!(
+            "| {} |\n",
+            row.iter()
+                .map(|cell| escape(cell))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for export of completed challenges
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    // Read rows from mock
+    fn rows() -> Vec<ExportRow> {
+        fixtures::completed()
+            .data
+            .iter()
+            .map(ExportRow::new)
+            .collect()
+    }
+
+    // Write rows to string
+    fn render(rows: &[ExportRow], format: ExportFormat) -> String {
+        let mut output = Vec::new();
+        write(rows, format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Test exporting to all formats without enrichment
+    #[test]
+    fn test_export_formats() {
+        let rows = rows();
+        assert_eq!(
+            render(&rows, ExportFormat::Csv),
+            "id,name,slug,completed_at,languages\n\
+             514b92a657cdc65150000006,Multiples of 3 and 5,multiples-of-3-and-5,2017-04-06T16:32:09Z,\"coffeescript, javascript, ruby\"\n"
+        );
+        assert_eq!(
+            render(&rows, ExportFormat::JsonLines),
+            "{\"id\":\"514b92a657cdc65150000006\",\"name\":\"Multiples of 3 and 5\",\
+             \"slug\":\"multiples-of-3-and-5\",\"completed_at\":\"2017-04-06T16:32:09Z\",\
+             \"languages\":[\"coffeescript\",\"javascript\",\"ruby\"]}\n"
+        );
+        assert_eq!(
+            render(&rows, ExportFormat::Markdown),
+            "| id | name | slug | completed_at | languages |\n\
+             | --- | --- | --- | --- | --- |\n\
+             | 514b92a657cdc65150000006 | Multiples of 3 and 5 | multiples-of-3-and-5 | 2017-04-06T16:32:09Z | coffeescript, javascript, ruby |\n"
+        );
+    }
+
+    /// Test enriching rows with rank and tags
+    #[tokio::test]
+    async fn test_enrich() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut rows = rows();
+        rows.push(ExportRow {
+            id: "deleted".to_string(),
+            name: "Deleted | kata".to_string(),
+            ..Default::default()
+        });
+        server
+            .mock("GET", "/api/v1/code-challenges/514b92a657cdc65150000006")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::KATA)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/code-challenges/deleted")
+            .with_status(404)
+            .create_async()
+            .await;
+        enrich(&client, &mut rows).await.unwrap();
+        assert_eq!(rows[0].rank, Some("4 kyu".to_string()));
+        assert_eq!(rows[1].tags, None);
+        let markdown = render(&rows, ExportFormat::Markdown);
+        assert!(
+            markdown.starts_with("| id | name | slug | completed_at | languages | rank | tags |\n")
+        );
+        assert!(markdown.contains("| 4 kyu | Algorithms, Validation, Logic, Utilities |\n"));
+        assert!(markdown.contains("| deleted | Deleted \\| kata |"));
+    }
+}
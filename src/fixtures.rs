@@ -0,0 +1,89 @@
+//! Fixtures from Codewars documentation for unit tests
+//!
+//! Raw JSON is meant for mock servers, parsed models for everything else. Both come from
+//! `tests/mocks`, the same data [`FakeCodewarsApi::with_fixtures`] is seeded with.
+//!
+//! [`FakeCodewarsApi::with_fixtures`]: crate::rest_api::fake::FakeCodewarsApi::with_fixtures
+
+pub(crate) use crate::rest_api::fake::{
+    AUTHORED_FIXTURE as AUTHORED, COMPLETED_FIXTURE as COMPLETED, KATA_FIXTURE as KATA,
+    USER_FIXTURE as USER,
+};
+use crate::rest_api::models::{
+    CodeChallenge, CompletedChallenge, CompletedChallenges, KataRank, Rank, User,
+};
+
+/// User `some_user`
+pub(crate) fn user() -> User {
+    serde_json::from_str(USER).unwrap()
+}
+
+/// Kata `valid-braces`
+pub(crate) fn kata() -> CodeChallenge {
+    serde_json::from_str(KATA).unwrap()
+}
+
+/// First page of completed challenges of `some_user`
+pub(crate) fn completed() -> CompletedChallenges {
+    serde_json::from_str(COMPLETED).unwrap()
+}
+
+/// User `some_user` renamed, with ranks only in the given languages
+pub(crate) fn user_with(username: &str, languages: &[&str]) -> User {
+    let mut user = user();
+    user.username = username.to_string();
+    user.ranks
+        .languages
+        .retain(|language, _| languages.contains(&language.as_str()));
+    user
+}
+
+/// Kata `valid-braces` with the given ID, rank, languages and tags, named after the ID
+pub(crate) fn kata_with(
+    id: &str,
+    rank: Option<i8>,
+    languages: &[&str],
+    tags: &[&str],
+) -> CodeChallenge {
+    CodeChallenge {
+        id: id.to_string(),
+        name: id.to_uppercase(),
+        rank: rank.map(|id| {
+            let rank = Rank::from_value(id, 0);
+            KataRank {
+                id,
+                name: rank.name,
+                color: rank.color,
+            }
+        }),
+        languages: languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        ..kata()
+    }
+}
+
+/// Completed katas with the given IDs, named after them
+pub(crate) fn completed_ids(ids: &[&str]) -> Vec<CompletedChallenge> {
+    ids.iter()
+        .map(|id| CompletedChallenge {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            slug: id.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completed katas with the given completion times
+pub(crate) fn completed_at(times: &[&str]) -> Vec<CompletedChallenge> {
+    times
+        .iter()
+        .map(|time| CompletedChallenge {
+            completed_at: time.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
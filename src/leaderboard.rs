@@ -0,0 +1,512 @@
+//! Leaderboard of a team of users
+//!
+//! Leaderboards can be saved to a JSON file, so the next leaderboard shows movement of members
+//! since the previous one.
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{CompletedChallenge, User};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Maximum number of members fetched at the same time
+const CONCURRENCY: usize = 4;
+
+/// Metric members of the leaderboard are ranked by
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Honor of the user
+    Honor,
+    /// Score of the overall rank
+    OverallScore,
+    /// Score of the rank in the language. Users without the rank have score 0
+    LanguageScore(String),
+    /// Number of katas completed in the window, including start and excluding end
+    Completions {
+        /// Start of the window
+        from: DateTime<Utc>,
+        /// End of the window
+        to: DateTime<Utc>,
+    },
+}
+
+/// Represents a member of the leaderboard
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct LeaderboardEntry {
+    /// Position of the member, starting from 1. Members with equal values share the position
+    pub position: u64,
+    /// Username of the member
+    pub username: String,
+    /// Value of the metric
+    pub value: u64,
+    /// Change of the position since the previous leaderboard. Positive value means moving up.
+    /// `None` if member wasn't in the previous leaderboard
+    pub movement: Option<i64>,
+}
+
+/// Represents a leaderboard of a team
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Leaderboard {
+    /// Metric members are ranked by
+    pub metric: LeaderboardMetric,
+    /// Date and time when the leaderboard was generated
+    pub generated_at: DateTime<Utc>,
+    /// Members from the first position
+    pub entries: Vec<LeaderboardEntry>,
+    /// Errors of members which couldn't be fetched, keyed by username
+    #[serde(default)]
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Implementation of Leaderboard
+impl Leaderboard {
+    /// Create leaderboard from values of the metric
+    ///
+    /// # Arguments:
+    /// * metric (LeaderboardMetric) - metric of the values
+    /// * values (Vec<(String, u64)>) - usernames of members and their values
+    /// * generated_at (DateTime<Utc>) - date and time when the values were fetched
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::Utc;
+    /// use codewars_api::leaderboard::{Leaderboard, LeaderboardMetric};
+    ///
+    /// let values = vec![
+    ///     ("alice".to_string(), 120),
+    ///     ("bob".to_string(), 300),
+    ///     ("carol".to_string(), 120),
+    /// ];
+    /// let leaderboard = Leaderboard::new(LeaderboardMetric::Honor, values, Utc::now());
+    /// let positions: Vec<_> = leaderboard
+    ///     .entries
+    ///     .iter()
+    ///     .map(|entry| (entry.position, entry.username.as_str()))
+    ///     .collect();
+    /// assert_eq!(positions, vec![(1, "bob"), (2, "alice"), (2, "carol")]);
+    /// ```
+    pub fn new(
+        metric: LeaderboardMetric,
+        mut values: Vec<(String, u64)>,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        values.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut entries: Vec<LeaderboardEntry> = Vec::new();
+        for (index, (username, value)) in values.into_iter().enumerate() {
+            let position = match entries.last() {
+                Some(last) if last.value == value => last.position,
+                _ => index as u64 + 1,
+            };
+            entries.push(LeaderboardEntry {
+                position,
+                username,
+                value,
+                movement: None,
+            });
+        }
+        Self {
+            metric,
+            generated_at,
+            entries,
+            errors: BTreeMap::new(),
+        }
+    }
+
+    /// Set movement of members since the previous leaderboard
+    ///
+    /// Leaderboards by [`LeaderboardMetric::Completions`] can be compared even if their windows
+    /// differ, e.g. to show movement since the previous week
+    ///
+    /// # Errors:
+    /// * `previous leaderboard has different metric: {metric:?}` - If metrics of leaderboards differ
+    pub fn compare_with(&mut self, previous: &Leaderboard) -> Result<(), String> {
+        let same_metric = match (&self.metric, &previous.metric) {
+            (LeaderboardMetric::Completions { .. }, LeaderboardMetric::Completions { .. }) => true,
+            (metric, previous) => metric == previous,
+        };
+        if !same_metric {
+            return Err(format!(
+                "previous leaderboard has different metric: {:?}",
+                previous.metric
+            ));
+        }
+        let positions: HashMap<&str, u64> = previous
+            .entries
+            .iter()
+            .map(|entry| (entry.username.as_str(), entry.position))
+            .collect();
+        for entry in &mut self.entries {
+            entry.movement = positions
+                .get(entry.username.as_str())
+                .map(|position| *position as i64 - entry.position as i64);
+        }
+        Ok(())
+    }
+
+    /// Load leaderboard from JSON file
+    ///
+    /// # Errors:
+    /// * Error of reading the file, e.g. if it doesn't exist
+    /// * Error of parsing JSON
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        serde_json::from_str(&content).map_err(|err| err.to_string())
+    }
+
+    /// Save leaderboard to JSON file, replacing its content
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        std::fs::write(path, content).map_err(|err| err.to_string())
+    }
+
+    /// Render leaderboard as Markdown table, followed by errors of members if there are any
+    pub fn to_markdown(&self) -> String {
+        let mut output =
+            String::from("| # | Member | Value | Movement |\n| --- | --- | --- | --- |\n");
+        for entry in &self.entries {
+            let movement = match entry.movement {
+                Some(0) => "=".to_string(),
+                Some(movement) => format!("{:+}", movement),
+                None => "new".to_string(),
+            };
+            writeln!(
+                output,
+                "| {} | {} | {} | {} |",
+                entry.position,
+                entry.username.replace('|', "\\|"),
+                entry.value,
+                movement
+            )
+            .unwrap();
+        }
+        if !self.errors.is_empty() {
+            output.push_str("\n## Errors\n\n| Member | Error |\n| --- | --- |\n");
+            for (username, err) in &self.errors {
+                writeln!(
+                    output,
+                    "| {} | {} |",
+                    username.replace('|', "\\|"),
+                    err.replace('|', "\\|")
+                )
+                .unwrap();
+            }
+        }
+        output
+    }
+}
+
+/// Get value of the metric for the user
+///
+/// # Arguments:
+/// * metric (&LeaderboardMetric) - metric to compute
+/// * user (&User) - user to compute the metric for
+/// * completed (&[CompletedChallenge]) - completed katas of the user, used only by
+///   [`LeaderboardMetric::Completions`]
+///
+/// # Errors:
+/// * `invalid completion time: {completed_at}` - If completion time is not in RFC 3339 format
+pub fn metric_value(
+    metric: &LeaderboardMetric,
+    user: &User,
+    completed: &[CompletedChallenge],
+) -> Result<u64, String> {
+    match metric {
+        LeaderboardMetric::Honor => Ok(user.honor),
+        LeaderboardMetric::OverallScore => Ok(user.ranks.overall.score),
+        LeaderboardMetric::LanguageScore(language) => Ok(user
+            .ranks
+            .languages
+            .get(language)
+            .map(|rank| rank.score)
+            .unwrap_or(0)),
+        LeaderboardMetric::Completions { from, to } => {
+            let mut count = 0;
+            for completion in completed {
+                let time = DateTime::parse_from_rfc3339(&completion.completed_at)
+                    .map_err(|_| format!("invalid completion time: {}", completion.completed_at))?;
+                if time >= *from && time < *to {
+                    count += 1;
+                }
+            }
+            Ok(count)
+        }
+    }
+}
+
+/// Fetch completed katas of the user up to the page which contains completions before the time
+async fn completed_since(
+    client: &dyn CodewarsApi,
+    username: &str,
+    from: DateTime<Utc>,
+) -> Result<Vec<CompletedChallenge>, String> {
+    let mut completed = Vec::new();
+    let mut page = 0;
+    loop {
+        let challenges = client.get_completed_challenges(username, page).await?;
+        // Completions are sorted from the newest, so the rest of pages is before the time
+        let reached_start = challenges.data.iter().any(|kata| {
+            DateTime::parse_from_rfc3339(&kata.completed_at).is_ok_and(|time| time < from)
+        });
+        completed.extend(challenges.data);
+        page += 1;
+        if reached_start || u64::from(page) >= challenges.total_pages {
+            return Ok(completed);
+        }
+    }
+}
+
+/// Fetch members of the roster and build leaderboard
+///
+/// Members are fetched concurrently. Completed katas are fetched only for
+/// [`LeaderboardMetric::Completions`], from the newest up to the start of the window.
+/// Members which couldn't be fetched are listed in [`Leaderboard::errors`], others are ranked
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * roster (&[&str]) - usernames of members
+/// * metric (LeaderboardMetric) - metric members are ranked by
+/// * previous (Option<&Leaderboard>) - previous leaderboard to compute movement from
+///
+/// # Errors:
+/// * Errors of [`Leaderboard::compare_with`]
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::leaderboard::{build_leaderboard, Leaderboard, LeaderboardMetric};
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let previous = Leaderboard::load("leaderboard.json").ok();
+/// let leaderboard = build_leaderboard(
+///     &client,
+///     &["ANKDDEV", "Dentzil"],
+///     LeaderboardMetric::Honor,
+///     previous.as_ref(),
+/// )
+/// .await
+/// .unwrap();
+/// println!("{}", leaderboard.to_markdown());
+/// leaderboard.save("leaderboard.json").unwrap();
+/// # }
+/// ```
+pub async fn build_leaderboard(
+    client: &dyn CodewarsApi,
+    roster: &[&str],
+    metric: LeaderboardMetric,
+    previous: Option<&Leaderboard>,
+) -> Result<Leaderboard, String> {
+    let metric = &metric;
+    let results: Vec<_> = stream::iter(roster)
+        .map(|username| async move {
+            let value = async {
+                let user = client.get_user(username).await?;
+                let completed = match metric {
+                    LeaderboardMetric::Completions { from, .. } => {
+                        completed_since(client, username, *from).await?
+                    }
+                    _ => Vec::new(),
+                };
+                let value = metric_value(metric, &user, &completed)?;
+                Ok::<_, String>((user.username, value))
+            };
+            (*username, value.await)
+        })
+        .buffer_unordered(CONCURRENCY)
+        .collect()
+        .await;
+    let mut values = Vec::new();
+    let mut errors = BTreeMap::new();
+    for (username, result) in results {
+        match result {
+            Ok(value) => values.push(value),
+            Err(err) => {
+                errors.insert(username.to_string(), err);
+            }
+        }
+    }
+    let mut leaderboard = Leaderboard {
+        errors,
+        ..Leaderboard::new(metric.clone(), values, Utc::now())
+    };
+    if let Some(previous) = previous {
+        leaderboard.compare_with(previous)?;
+    }
+    Ok(leaderboard)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for leaderboard
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+    use crate::rest_api::models::CompletedChallenges;
+    use chrono::TimeZone;
+
+    /// Test movement since previous leaderboard, saved to file
+    #[test]
+    fn test_movement() {
+        let time = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let metric = LeaderboardMetric::LanguageScore("rust".to_string());
+        let values = |values: &[(&str, u64)]| {
+            values
+                .iter()
+                .map(|(username, value)| (username.to_string(), *value))
+                .collect()
+        };
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("leaderboard.json");
+        Leaderboard::new(
+            metric.clone(),
+            values(&[("alice", 300), ("bob", 200), ("carol", 100)]),
+            time,
+        )
+        .save(&path)
+        .unwrap();
+        let previous = Leaderboard::load(&path).unwrap();
+        assert_eq!(previous.metric, metric);
+        let mut leaderboard = Leaderboard::new(
+            metric,
+            values(&[("alice", 300), ("bob", 200), ("carol", 400), ("dave", 10)]),
+            time,
+        );
+        leaderboard.compare_with(&previous).unwrap();
+        assert_eq!(
+            leaderboard.to_markdown(),
+            "| # | Member | Value | Movement |\n\
+             | --- | --- | --- | --- |\n\
+             | 1 | carol | 400 | +2 |\n\
+             | 2 | alice | 300 | -1 |\n\
+             | 3 | bob | 200 | -1 |\n\
+             | 4 | dave | 10 | new |\n"
+        );
+    }
+
+    /// Test counting completions in the window
+    #[test]
+    fn test_completions_metric() {
+        let user = fixtures::user();
+        let completed = fixtures::completed_at(&[
+            "2024-01-01T00:00:00Z",
+            "2024-01-05T12:00:00Z",
+            "2024-01-08T00:00:00Z",
+        ]);
+        let metric = LeaderboardMetric::Completions {
+            from: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            to: Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap(),
+        };
+        assert_eq!(metric_value(&metric, &user, &completed), Ok(2));
+        assert_eq!(
+            metric_value(&LeaderboardMetric::Honor, &user, &[]),
+            Ok(user.honor)
+        );
+    }
+
+    /// Test that leaderboards by different metrics are not compared
+    #[test]
+    fn test_compare_different_metrics() {
+        let time = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let values = vec![("alice".to_string(), 100)];
+        let previous = Leaderboard::new(LeaderboardMetric::Honor, values.clone(), time);
+        let mut leaderboard =
+            Leaderboard::new(LeaderboardMetric::OverallScore, values.clone(), time);
+        assert_eq!(
+            leaderboard.compare_with(&previous),
+            Err("previous leaderboard has different metric: Honor".to_string())
+        );
+        assert_eq!(leaderboard.entries[0].movement, None);
+
+        let week = |day| LeaderboardMetric::Completions {
+            from: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            to: Utc.with_ymd_and_hms(2024, 1, day + 7, 0, 0, 0).unwrap(),
+        };
+        let previous = Leaderboard::new(week(1), values.clone(), time);
+        let mut leaderboard = Leaderboard::new(week(8), values, time);
+        assert_eq!(leaderboard.compare_with(&previous), Ok(()));
+        assert_eq!(leaderboard.entries[0].movement, Some(0));
+    }
+
+    /// Test that pages of completed katas before the window are not fetched
+    #[tokio::test]
+    async fn test_build_completions_leaderboard() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let completed_path = "/api/v1/users/some_user/code-challenges/completed";
+        // Build page of completed katas with the given completion times
+        let page = |times: &[&str]| {
+            serde_json::to_string(&CompletedChallenges {
+                total_pages: 3,
+                total_items: 6,
+                data: fixtures::completed_at(times),
+            })
+            .unwrap()
+        };
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock("GET", format!("{}?page=0", completed_path).as_str())
+            .with_body(page(&["2024-01-09T00:00:00Z", "2024-01-05T00:00:00Z"]))
+            .create_async()
+            .await;
+        server
+            .mock("GET", format!("{}?page=1", completed_path).as_str())
+            .with_body(page(&["2024-01-03T00:00:00Z", "2023-12-30T00:00:00Z"]))
+            .create_async()
+            .await;
+        let third_page = server
+            .mock("GET", format!("{}?page=2", completed_path).as_str())
+            .expect(0)
+            .create_async()
+            .await;
+        let metric = LeaderboardMetric::Completions {
+            from: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            to: Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap(),
+        };
+        let leaderboard = build_leaderboard(&client, &["some_user"], metric, None)
+            .await
+            .unwrap();
+        assert_eq!(leaderboard.entries[0].value, 2);
+        third_page.assert_async().await;
+    }
+
+    /// Test that members which couldn't be fetched don't fail the leaderboard
+    #[tokio::test]
+    async fn test_member_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+        let leaderboard = build_leaderboard(
+            &client,
+            &["missing", "some_user"],
+            LeaderboardMetric::Honor,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(leaderboard.entries.len(), 1);
+        assert_eq!(leaderboard.entries[0].username, "some_user");
+        assert!(leaderboard.errors["missing"].starts_with("unexpected status code: 404"));
+        assert!(leaderboard
+            .to_markdown()
+            .contains("## Errors\n\n| Member | Error |\n| --- | --- |\n| missing | "));
+    }
+}
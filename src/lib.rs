@@ -6,7 +6,32 @@
 // Warn if something is not documented
 #![warn(missing_docs)]
 
+pub mod analytics;
+pub mod badge;
+pub mod clan;
+pub mod compare;
+#[cfg(feature = "tui")]
+pub mod dashboard;
+pub mod description;
+pub mod diff;
+pub mod display;
+pub mod export;
+#[cfg(test)]
+mod fixtures;
+pub mod leaderboard;
+pub mod metrics;
+pub mod notify;
+pub mod recommend;
 pub mod rest_api;
+pub mod scaffold;
+pub mod snapshots;
+#[cfg(feature = "sync")]
+pub mod sync;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod translations;
+pub mod watcher;
+pub mod webhooks;
 
 // Re-exports
 pub use crate::rest_api::client::RestCodewarsClient;
@@ -14,3 +39,6 @@ pub use crate::rest_api::models::AuthoredChallenges;
 pub use crate::rest_api::models::CodeChallenge;
 pub use crate::rest_api::models::CompletedChallenges;
 pub use crate::rest_api::models::User;
+pub use crate::diff::UserDiff;
+pub use crate::watcher::UserWatcher;
+pub use crate::webhooks::models::WebhookEvent;
@@ -0,0 +1,294 @@
+//! Prometheus metrics of tracked users
+//!
+//! [`UserMetrics`] keeps the last fetched profile of every tracked user and renders gauges in
+//! Prometheus text format. If a user can't be fetched, last known values are kept and
+//! `codewars_up` of the user is set to 0.
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::User;
+use futures_util::stream::{self, StreamExt};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Content type of Prometheus text format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Name, help and value of a gauge with one sample per user
+type UserGauge = (&'static str, &'static str, fn(&User) -> u64);
+
+/// Gauges with one sample per user
+const USER_GAUGES: [UserGauge; 5] = [
+    ("codewars_honor", "Honor of the user", |user| user.honor),
+    (
+        "codewars_leaderboard_position",
+        "Position of the user on the overall leaderboard",
+        |user| user.leaderboard_position,
+    ),
+    (
+        "codewars_overall_score",
+        "Overall rank score of the user",
+        |user| user.ranks.overall.score,
+    ),
+    (
+        "codewars_completed_katas",
+        "Total number of katas completed by the user",
+        |user| user.code_challenges.total_completed,
+    ),
+    (
+        "codewars_authored_katas",
+        "Total number of katas authored by the user",
+        |user| user.code_challenges.total_authored,
+    ),
+];
+
+/// Represents last fetched state of a tracked user
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct TrackedUser {
+    // Last successfully fetched profile
+    user: Option<User>,
+    // Whether the last fetch succeeded
+    up: bool,
+}
+
+/// Metrics of tracked users
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct UserMetrics {
+    users: BTreeMap<String, TrackedUser>,
+}
+
+/// Implementation of UserMetrics
+impl UserMetrics {
+    /// Create metrics for the users. Users are not fetched until [`UserMetrics::refresh`] is
+    /// called
+    pub fn new(usernames: &[&str]) -> Self {
+        Self {
+            users: usernames
+                .iter()
+                .map(|username| (username.to_string(), TrackedUser::default()))
+                .collect(),
+        }
+    }
+
+    /// Update the user with result of fetching it
+    ///
+    /// Last known profile is kept if fetching failed
+    pub fn update(&mut self, username: &str, result: Result<User, String>) {
+        let tracked = self.users.entry(username.to_string()).or_default();
+        match result {
+            Ok(user) => {
+                tracked.user = Some(user);
+                tracked.up = true;
+            }
+            Err(_) => tracked.up = false,
+        }
+    }
+
+    /// Fetch all tracked users
+    ///
+    /// # Arguments:
+    /// * client (&dyn CodewarsApi) - client to fetch users with
+    /// * concurrency (usize) - maximum number of requests sent at the same time
+    ///
+    /// Returns errors of users which couldn't be fetched, keyed by username
+    pub async fn refresh(
+        &mut self,
+        client: &dyn CodewarsApi,
+        concurrency: usize,
+    ) -> BTreeMap<String, String> {
+        let results: Vec<(String, Result<User, String>)> =
+            stream::iter(self.users.keys().cloned().collect::<Vec<_>>())
+                .map(|username| async move {
+                    let result = client.get_user(&username).await;
+                    (username, result)
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+        let mut errors = BTreeMap::new();
+        for (username, result) in results {
+            if let Err(err) = &result {
+                errors.insert(username.clone(), err.clone());
+            }
+            self.update(&username, result);
+        }
+        errors
+    }
+
+    /// Render metrics in Prometheus text format
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::metrics::UserMetrics;
+    /// use codewars_api::RestCodewarsClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = RestCodewarsClient::new();
+    /// let mut metrics = UserMetrics::new(&["ANKDDEV"]);
+    /// metrics.refresh(&client, 4).await;
+    /// print!("{}", metrics.render());
+    /// # }
+    /// ```
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        gauge(
+            &mut output,
+            "codewars_up",
+            "Whether the last fetch of the user succeeded",
+            self.users
+                .iter()
+                .map(|(username, tracked)| (labels(username, None), tracked.up as u64)),
+        );
+        // Samples are labeled with tracked usernames, which can differ from usernames in
+        // profiles, e.g. by case, so all gauges of a user have the same labels
+        let users: Vec<(&str, &User)> = self
+            .users
+            .iter()
+            .filter_map(|(username, tracked)| Some((username.as_str(), tracked.user.as_ref()?)))
+            .collect();
+        for (name, help, value) in USER_GAUGES {
+            gauge(
+                &mut output,
+                name,
+                help,
+                users
+                    .iter()
+                    .map(|(username, user)| (labels(username, None), value(user))),
+            );
+        }
+        let mut languages = Vec::new();
+        for (username, user) in &users {
+            let mut ranks: Vec<_> = user.ranks.languages.iter().collect();
+            ranks.sort_by(|a, b| a.0.cmp(b.0));
+            languages.extend(
+                ranks
+                    .into_iter()
+                    .map(|(language, rank)| (labels(username, Some(language)), rank.score)),
+            );
+        }
+        gauge(
+            &mut output,
+            "codewars_language_score",
+            "Rank score of the user in the language",
+            languages.into_iter(),
+        );
+        output
+    }
+}
+
+// Write gauge with help, type and samples
+fn gauge(
+    output: &mut String,
+    name: &str,
+    help: &str,
+    samples: impl Iterator<Item = (String, u64)>,
+) {
+    writeln!(output, "# HELP {} {}", name, help).unwrap();
+    writeln!(output, "# TYPE {} gauge", name).unwrap();
+    for (labels, value) in samples {
+        writeln!(output, "{}{{{}}} {}", name, labels, value).unwrap();
+    }
+}
+
+// Format labels of the user and optionally of the language
+fn labels(username: &str, language: Option<&str>) -> String {
+    let mut labels = format!("username=\"{}\"", escape(username));
+    if let Some(language) = language {
+        write!(labels, ",language=\"{}\"", escape(language)).unwrap();
+    }
+    labels
+}
+
+// Escape label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for Prometheus metrics
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    /// Test refreshing users and rendering metrics
+    #[tokio::test]
+    async fn test_refresh_and_render() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+        let mut metrics = UserMetrics::new(&["some_user", "missing"]);
+        let errors = metrics.refresh(&client, 2).await;
+        assert_eq!(
+            errors.keys().collect::<Vec<_>>(),
+            vec![&"missing".to_string()]
+        );
+        let expected = "\
+# HELP codewars_up Whether the last fetch of the user succeeded
+# TYPE codewars_up gauge
+codewars_up{username=\"missing\"} 0
+codewars_up{username=\"some_user\"} 1
+# HELP codewars_honor Honor of the user
+# TYPE codewars_honor gauge
+codewars_honor{username=\"some_user\"} 544
+# HELP codewars_leaderboard_position Position of the user on the overall leaderboard
+# TYPE codewars_leaderboard_position gauge
+codewars_leaderboard_position{username=\"some_user\"} 134
+# HELP codewars_overall_score Overall rank score of the user
+# TYPE codewars_overall_score gauge
+codewars_overall_score{username=\"some_user\"} 2116
+# HELP codewars_completed_katas Total number of katas completed by the user
+# TYPE codewars_completed_katas gauge
+codewars_completed_katas{username=\"some_user\"} 230
+# HELP codewars_authored_katas Total number of katas authored by the user
+# TYPE codewars_authored_katas gauge
+codewars_authored_katas{username=\"some_user\"} 3
+# HELP codewars_language_score Rank score of the user in the language
+# TYPE codewars_language_score gauge
+codewars_language_score{username=\"some_user\",language=\"coffeescript\"} 870
+codewars_language_score{username=\"some_user\",language=\"javascript\"} 1819
+codewars_language_score{username=\"some_user\",language=\"ruby\"} 1005
+";
+        assert_eq!(metrics.render(), expected);
+
+        // Last known values are kept when fetching fails
+        metrics.update("some_user", Err("unexpected status code: 500".to_string()));
+        let output = metrics.render();
+        assert!(output.contains("codewars_up{username=\"some_user\"} 0\n"));
+        assert!(output.contains("codewars_honor{username=\"some_user\"} 544\n"));
+    }
+
+    /// Test that samples are labeled with tracked usernames
+    #[test]
+    fn test_tracked_username_labels() {
+        let mut metrics = UserMetrics::new(&["Some_User"]);
+        metrics.update("Some_User", Ok(fixtures::user()));
+        let output = metrics.render();
+        assert!(output.contains("codewars_up{username=\"Some_User\"} 1\n"));
+        assert!(output.contains("codewars_honor{username=\"Some_User\"} 544\n"));
+        assert!(!output.contains("username=\"some_user\""));
+    }
+
+    /// Test escaping of label values
+    #[test]
+    fn test_labels() {
+        assert_eq!(
+            labels("a\"b", Some("c\\d\n")),
+            "username=\"a\\\"b\",language=\"c\\\\d\\n\""
+        );
+    }
+}
@@ -0,0 +1,502 @@
+//! Chat notifications about rank-ups and completions
+//!
+//! [`Notification`]s are built from a [`UserDiff`] of two snapshots or from a [`WebhookEvent`],
+//! then formatted with [`ChatFormat`] as Slack Block Kit, Discord embed or Microsoft Teams card
+//! payloads. Payloads are colored with the color of the rank, see [`rank_color`].
+//! [`NotificationSender`] posts them to an incoming webhook.
+
+use crate::badge::rank_color;
+use crate::diff::UserDiff;
+use crate::rest_api::models::{Color, Rank};
+use crate::webhooks::models::{UserEvent, WebhookEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Color of notifications which are not about a rank
+const DEFAULT_COLOR: &str = "#b1361e";
+
+/// Represents a notification about a change of a user
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Notification {
+    /// Username of the user, or ID if the notification is built from a webhook event
+    pub username: String,
+    /// Short title, e.g. `some_user reached 2 kyu`
+    pub title: String,
+    /// Longer text of the notification
+    pub text: String,
+    /// Named values shown below the text
+    pub fields: Vec<(String, String)>,
+    /// Color of the rank the notification is about, if it is known
+    pub color: Option<Color>,
+    /// Link to the profile of the user
+    pub url: Option<String>,
+}
+
+/// Implementation of Notification
+impl Notification {
+    /// Build notifications about rank-ups, new languages and completed katas from a difference
+    /// of two snapshots
+    ///
+    /// Returns no notifications if nothing of this has changed
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::diff::UserDiff;
+    /// use codewars_api::notify::{ChatFormat, Notification, NotificationSender};
+    /// use codewars_api::RestCodewarsClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = RestCodewarsClient::new();
+    /// let old = client.get_user("ANKDDEV").await.unwrap();
+    /// // Some time later
+    /// let new = client.get_user("ANKDDEV").await.unwrap();
+    /// let sender = NotificationSender::new("https://hooks.slack.com/services/...", ChatFormat::Slack);
+    /// for notification in Notification::from_diff(&UserDiff::new(&old, &new)) {
+    ///     sender.send(&notification).await.unwrap();
+    /// }
+    /// # }
+    /// ```
+    pub fn from_diff(diff: &UserDiff) -> Vec<Notification> {
+        let url = Some(format!("https://www.codewars.com/users/{}", diff.username));
+        let mut notifications = Vec::new();
+        if let Some(rank) = diff
+            .overall_rank
+            .as_ref()
+            .filter(|rank| rank.is_rank_changed())
+        {
+            notifications.push(Notification {
+                username: diff.username.clone(),
+                title: format!("{} reached {}", diff.username, rank.after.name),
+                text: format!(
+                    "Overall rank changed from {} to {}",
+                    rank.before.name, rank.after.name
+                ),
+                fields: vec![
+                    ("Score".to_string(), rank.after.score.to_string()),
+                    ("Honor".to_string(), diff.honor.after.to_string()),
+                ],
+                color: Some(rank.after.color.clone()),
+                url: url.clone(),
+            });
+        }
+        for (language, rank) in &diff.languages {
+            if rank.is_rank_changed() {
+                notifications.push(Notification {
+                    username: diff.username.clone(),
+                    title: format!(
+                        "{} reached {} in {}",
+                        diff.username, rank.after.name, language
+                    ),
+                    text: format!(
+                        "{} rank changed from {} to {}",
+                        language, rank.before.name, rank.after.name
+                    ),
+                    fields: vec![("Score".to_string(), rank.after.score.to_string())],
+                    color: Some(rank.after.color.clone()),
+                    url: url.clone(),
+                });
+            }
+        }
+        for (language, rank) in &diff.new_languages {
+            notifications.push(Notification {
+                username: diff.username.clone(),
+                title: format!("{} started training {}", diff.username, language),
+                text: format!("Earned {} in {}", rank.name, language),
+                fields: vec![("Score".to_string(), rank.score.to_string())],
+                color: Some(rank.color.clone()),
+                url: url.clone(),
+            });
+        }
+        if diff.total_completed.delta > 0 {
+            let completed = diff.total_completed.delta;
+            notifications.push(Notification {
+                username: diff.username.clone(),
+                title: format!(
+                    "{} completed {} {}",
+                    diff.username,
+                    completed,
+                    if completed == 1 { "kata" } else { "katas" }
+                ),
+                text: format!("{} katas completed in total", diff.total_completed.after),
+                fields: vec![("Honor".to_string(), diff.honor.to_string())],
+                color: diff
+                    .overall_rank
+                    .as_ref()
+                    .map(|rank| rank.after.color.clone()),
+                url,
+            });
+        }
+        notifications
+    }
+
+    /// Build notification from a webhook event
+    ///
+    /// Returns `None` for events about code challenges
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::notify::Notification;
+    /// use codewars_api::rest_api::models::Color;
+    /// use codewars_api::webhooks::models::WebhookEvent;
+    ///
+    /// let body = r#"{"action":"rank_upgraded","user":{"id":"5a1b","rank":-2}}"#;
+    /// let event = WebhookEvent::parse("user", body).unwrap();
+    /// let notification = Notification::from_webhook(&event).unwrap();
+    /// assert_eq!(notification.title, "5a1b reached 2 kyu");
+    /// assert_eq!(notification.color, Some(Color::Purple));
+    /// ```
+    pub fn from_webhook(event: &WebhookEvent) -> Option<Notification> {
+        let WebhookEvent::User(event) = event else {
+            return None;
+        };
+        Some(match event {
+            UserEvent::RankUpgraded { user } | UserEvent::RankEarned { user } => {
+                let rank = Rank::from_value(user.rank, 0);
+                let (title, text) = match (event, &user.language) {
+                    (UserEvent::RankEarned { .. }, Some(language)) => (
+                        format!("{} started training {}", user.id, language),
+                        format!("Earned {} in {}", rank.name, language),
+                    ),
+                    (_, Some(language)) => (
+                        format!("{} reached {} in {}", user.id, rank.name, language),
+                        format!("{} rank upgraded to {}", language, rank.name),
+                    ),
+                    (_, None) => (
+                        format!("{} reached {}", user.id, rank.name),
+                        format!("Overall rank upgraded to {}", rank.name),
+                    ),
+                };
+                Notification {
+                    username: user.id.clone(),
+                    title,
+                    text,
+                    fields: Vec::new(),
+                    color: Some(rank.color),
+                    url: None,
+                }
+            }
+            UserEvent::HonorChanged { user } => Notification {
+                username: user.id.clone(),
+                title: format!("{} has {} honor", user.id, user.honor),
+                text: format!(
+                    "Honor changed by {:+}",
+                    user.honor as i64 - user.honor_was as i64
+                ),
+                fields: Vec::new(),
+                color: None,
+                url: None,
+            },
+        })
+    }
+
+    // Get hex color of the notification
+    fn hex_color(&self) -> &'static str {
+        self.color.as_ref().map_or(DEFAULT_COLOR, rank_color)
+    }
+}
+
+/// Format of chat payloads
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatFormat {
+    /// Slack Block Kit message in a colored attachment
+    Slack,
+    /// Discord message with an embed
+    Discord,
+    /// Microsoft Teams message card
+    Teams,
+}
+
+/// Implementation of ChatFormat
+impl ChatFormat {
+    /// Format the notification as a JSON payload of an incoming webhook
+    pub fn format(&self, notification: &Notification) -> Value {
+        let color = notification.hex_color();
+        match self {
+            ChatFormat::Slack => {
+                let mut text = format!(
+                    "*{}*\n{}",
+                    escape_mrkdwn(&notification.title),
+                    escape_mrkdwn(&notification.text)
+                );
+                if let Some(url) = &notification.url {
+                    text.push_str(&format!("\n<{}|View profile>", url));
+                }
+                let mut blocks = vec![json!({
+                    "type": "section",
+                    "text": {"type": "mrkdwn", "text": text},
+                })];
+                if !notification.fields.is_empty() {
+                    let fields: Vec<Value> = notification
+                        .fields
+                        .iter()
+                        .map(|(name, value)| {
+                            let text =
+                                format!("*{}*\n{}", escape_mrkdwn(name), escape_mrkdwn(value));
+                            json!({"type": "mrkdwn", "text": text})
+                        })
+                        .collect();
+                    blocks.push(json!({"type": "section", "fields": fields}));
+                }
+                json!({
+                    "text": escape_mrkdwn(&notification.title),
+                    "attachments": [{"color": color, "blocks": blocks}],
+                })
+            }
+            ChatFormat::Discord => {
+                let fields: Vec<Value> = notification
+                    .fields
+                    .iter()
+                    .map(|(name, value)| json!({"name": name, "value": value, "inline": true}))
+                    .collect();
+                let mut embed = json!({
+                    "title": notification.title,
+                    "description": notification.text,
+                    // Discord expects color as an integer
+                    "color": u32::from_str_radix(color.trim_start_matches('#'), 16).unwrap_or(0),
+                    "fields": fields,
+                });
+                if let Some(url) = &notification.url {
+                    embed["url"] = json!(url);
+                }
+                json!({"embeds": [embed]})
+            }
+            ChatFormat::Teams => {
+                let facts: Vec<Value> = notification
+                    .fields
+                    .iter()
+                    .map(|(name, value)| json!({"name": name, "value": value}))
+                    .collect();
+                let mut card = json!({
+                    "@type": "MessageCard",
+                    "@context": "https://schema.org/extensions",
+                    "themeColor": color.trim_start_matches('#'),
+                    "summary": notification.title,
+                    "title": notification.title,
+                    "text": notification.text,
+                    "sections": [{"facts": facts}],
+                });
+                if let Some(url) = &notification.url {
+                    card["potentialAction"] = json!([{
+                        "@type": "OpenUri",
+                        "name": "View profile",
+                        "targets": [{"os": "default", "uri": url}],
+                    }]);
+                }
+                card
+            }
+        }
+    }
+}
+
+// Escape control characters of Slack mrkdwn, so text can't add links or mentions
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Sends notifications to an incoming webhook of a chat
+#[derive(Debug, Clone)]
+pub struct NotificationSender {
+    url: String,
+    format: ChatFormat,
+    client: reqwest::Client,
+}
+
+/// Implementation of NotificationSender
+impl NotificationSender {
+    /// Create new sender
+    ///
+    /// # Arguments:
+    /// * url (&str) - URL of the incoming webhook
+    /// * format (ChatFormat) - format of payloads the chat expects
+    pub fn new(url: &str, format: ChatFormat) -> Self {
+        Self {
+            url: url.to_string(),
+            format,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Send notification to the webhook
+    ///
+    /// # Errors:
+    /// * `unexpected status code: {status_code}` - If the webhook responded with non-success status code
+    /// * `error sending request for url ({url})` - If the request can't be sent, e.g. because of network error
+    pub async fn send(&self, notification: &Notification) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&self.format.format(notification))
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+        // Check status code
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            other => Err(format!("unexpected status code: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for chat notifications
+
+    use super::*;
+    use crate::fixtures;
+
+    // Build notifications from the mock user and the same user after a rank-up
+    fn notifications() -> Vec<Notification> {
+        let old = fixtures::user();
+        let mut new = old.clone();
+        new.honor = 560;
+        new.ranks.overall = Rank::from_value(-2, 4900);
+        new.code_challenges.total_completed = 231;
+        Notification::from_diff(&UserDiff::new(&old, &new))
+    }
+
+    /// Test building notifications from a difference of snapshots
+    #[test]
+    fn test_from_diff() {
+        let notifications = notifications();
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].title, "some_user reached 2 kyu");
+        assert_eq!(notifications[0].color, Some(Color::Purple));
+        assert_eq!(notifications[1].title, "some_user completed 1 kata");
+        assert_eq!(
+            notifications[1].fields,
+            vec![("Honor".to_string(), "+16 (544 -> 560)".to_string())]
+        );
+    }
+
+    /// Test building notifications from webhook events
+    #[test]
+    fn test_from_webhook() {
+        let body = r#"{"action":"rank_earned","user":{"id":"5a1b","rank":-8,"language":"rust"}}"#;
+        let event = WebhookEvent::parse("user", body).unwrap();
+        let notification = Notification::from_webhook(&event).unwrap();
+        assert_eq!(notification.title, "5a1b started training rust");
+        assert_eq!(notification.text, "Earned 8 kyu in rust");
+        assert_eq!(notification.color, Some(Color::White));
+        let body = r#"{"action":"created","code_challenge":{"id":"1","created_by_id":"5a1b"}}"#;
+        let event = WebhookEvent::parse("code_challenge", body).unwrap();
+        assert_eq!(Notification::from_webhook(&event), None);
+    }
+
+    /// Test payloads of every chat
+    #[test]
+    fn test_format() {
+        let notification = &notifications()[0];
+        assert_eq!(
+            ChatFormat::Slack.format(notification),
+            json!({
+                "text": "some_user reached 2 kyu",
+                "attachments": [{
+                    "color": "#866cc7",
+                    "blocks": [
+                        {
+                            "type": "section",
+                            "text": {
+                                "type": "mrkdwn",
+                                "text": "*some_user reached 2 kyu*\nOverall rank changed from 3 kyu to 2 kyu\n<https://www.codewars.com/users/some_user|View profile>",
+                            },
+                        },
+                        {
+                            "type": "section",
+                            "fields": [
+                                {"type": "mrkdwn", "text": "*Score*\n4900"},
+                                {"type": "mrkdwn", "text": "*Honor*\n560"},
+                            ],
+                        },
+                    ],
+                }],
+            })
+        );
+        assert_eq!(
+            ChatFormat::Discord.format(notification),
+            json!({
+                "embeds": [{
+                    "title": "some_user reached 2 kyu",
+                    "description": "Overall rank changed from 3 kyu to 2 kyu",
+                    "color": 0x866cc7,
+                    "url": "https://www.codewars.com/users/some_user",
+                    "fields": [
+                        {"name": "Score", "value": "4900", "inline": true},
+                        {"name": "Honor", "value": "560", "inline": true},
+                    ],
+                }],
+            })
+        );
+        let card = ChatFormat::Teams.format(notification);
+        assert_eq!(card["themeColor"], "866cc7");
+        assert_eq!(
+            card["sections"][0]["facts"][1],
+            json!({"name": "Honor", "value": "560"})
+        );
+        assert_eq!(
+            card["potentialAction"][0]["targets"][0]["uri"],
+            "https://www.codewars.com/users/some_user"
+        );
+    }
+
+    /// Test sending notification to a webhook
+    #[tokio::test]
+    async fn test_send() {
+        let mut server = mockito::Server::new_async().await;
+        let notification = &notifications()[1];
+        let mock = server
+            .mock("POST", "/webhook")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::Json(
+                ChatFormat::Discord.format(notification),
+            ))
+            .with_status(204)
+            .create_async()
+            .await;
+        let sender =
+            NotificationSender::new(&format!("{}/webhook", server.url()), ChatFormat::Discord);
+        sender.send(notification).await.unwrap();
+        mock.assert_async().await;
+        let sender =
+            NotificationSender::new(&format!("{}/missing", server.url()), ChatFormat::Slack);
+        assert!(sender
+            .send(notification)
+            .await
+            .unwrap_err()
+            .starts_with("unexpected status code: 501"));
+        let sender = NotificationSender::new("http://127.0.0.1:1/webhook", ChatFormat::Slack);
+        assert!(sender
+            .send(notification)
+            .await
+            .unwrap_err()
+            .starts_with("error sending request for url (http://127.0.0.1:1/webhook)"));
+    }
+
+    /// Test escaping of Slack mrkdwn
+    #[test]
+    fn test_slack_escaping() {
+        let notification = Notification {
+            username: "<!channel>".to_string(),
+            title: "<!channel> & <https://evil.example|click>".to_string(),
+            text: "a > b".to_string(),
+            fields: vec![("<b>".to_string(), "1 & 2".to_string())],
+            color: None,
+            url: Some("https://www.codewars.com/users/some_user".to_string()),
+        };
+        let payload = ChatFormat::Slack.format(&notification);
+        assert_eq!(
+            payload["text"],
+            "&lt;!channel&gt; &amp; &lt;https://evil.example|click&gt;"
+        );
+        let blocks = &payload["attachments"][0]["blocks"];
+        assert_eq!(
+            blocks[0]["text"]["text"],
+            "*&lt;!channel&gt; &amp; &lt;https://evil.example|click&gt;*\na &gt; b\n\
+             <https://www.codewars.com/users/some_user|View profile>"
+        );
+        assert_eq!(blocks[1]["fields"][0]["text"], "*&lt;b&gt;*\n1 &amp; 2");
+    }
+}
@@ -0,0 +1,290 @@
+//! Recommendations of katas to solve next
+//!
+//! Candidate katas are ranked by how well their rank fits the user's rank in a language and by
+//! how underrepresented their tags are in the user's history. Every recommendation explains
+//! why the kata was picked.
+
+use crate::analytics::completions::CompletionStats;
+use crate::rest_api::models::{CodeChallenge, CompletedChallenge, Rank, User};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+/// Weight of rank fit in the score of a recommendation
+const RANK_WEIGHT: f64 = 0.6;
+/// Weight of underrepresented tags in the score of a recommendation
+const TAGS_WEIGHT: f64 = 0.4;
+
+/// Represents a reason why a kata was recommended
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case", tag = "reason")]
+pub enum Reason {
+    /// Rank of the kata is close to the rank of the user in the language
+    RankFit {
+        /// Language the kata fits in
+        language: String,
+        /// Name of the rank of the kata
+        kata_rank: String,
+        /// Name of the rank of the user in the language
+        user_rank: String,
+        /// Number of ranks the kata is above the user's rank, negative if it is below
+        difference: i8,
+    },
+    /// Kata has tags which are underrepresented in the user's history
+    UnderrepresentedTags {
+        /// Underrepresented tags of the kata
+        tags: Vec<String>,
+    },
+}
+
+/// Represents a recommended kata
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Recommendation {
+    /// ID of the kata
+    pub id: String,
+    /// Name of the kata
+    pub name: String,
+    /// URL of the kata
+    pub url: String,
+    /// Language to solve the kata in
+    pub language: String,
+    /// Score of the recommendation from 0 to 1. Higher is better
+    pub score: f64,
+    /// Reasons why the kata was recommended
+    pub reasons: Vec<Reason>,
+}
+
+/// Recommends katas based on the history of a user
+#[derive(Debug, Clone)]
+pub struct Recommender {
+    // Ranks of the user in languages
+    languages: HashMap<String, Rank>,
+    // IDs of completed katas
+    completed: HashSet<String>,
+    // Number of completed katas with each tag
+    tags: BTreeMap<String, u64>,
+}
+
+/// Implementation of Recommender
+impl Recommender {
+    /// Create recommender from the history of the user
+    ///
+    /// # Arguments:
+    /// * user (&User) - user to recommend katas to
+    /// * completed (&[CompletedChallenge]) - completed katas of the user, e.g. from `get_all_completed_challenges`
+    /// * katas (&HashMap<String, CodeChallenge>) - metadata of completed katas keyed by ID, used for tags
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::analytics::completions::fetch_katas;
+    /// use codewars_api::recommend::Recommender;
+    /// use codewars_api::RestCodewarsClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let client = RestCodewarsClient::new();
+    /// let user = client.get_user("ANKDDEV").await.unwrap();
+    /// let completed = client.get_all_completed_challenges("ANKDDEV").await.unwrap();
+    /// let katas = fetch_katas(&client, &completed).await.unwrap();
+    /// let candidates = vec![client.get_kata("valid-braces").await.unwrap()];
+    /// let recommender = Recommender::new(&user, &completed, &katas);
+    /// for recommendation in recommender.recommend(&candidates, 5) {
+    ///     println!("{} ({})", recommendation.name, recommendation.language);
+    ///     for reason in &recommendation.reasons {
+    ///         println!("  {}", reason);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn new(
+        user: &User,
+        completed: &[CompletedChallenge],
+        katas: &HashMap<String, CodeChallenge>,
+    ) -> Self {
+        Self {
+            languages: user.ranks.languages.clone(),
+            completed: completed.iter().map(|kata| kata.id.clone()).collect(),
+            tags: CompletionStats::new(completed, katas).per_tag,
+        }
+    }
+
+    /// Rank candidate katas and return the best ones
+    ///
+    /// Completed katas, katas without rank and katas without a fitting rank in any of the
+    /// user's languages are skipped
+    ///
+    /// # Arguments:
+    /// * candidates (&[CodeChallenge]) - katas to choose from
+    /// * limit (usize) - maximum number of recommendations
+    pub fn recommend(&self, candidates: &[CodeChallenge], limit: usize) -> Vec<Recommendation> {
+        let mut recommendations: Vec<Recommendation> = candidates
+            .iter()
+            .filter_map(|kata| self.recommendation(kata))
+            .collect();
+        recommendations.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.name.cmp(&b.name)));
+        recommendations.truncate(limit);
+        recommendations
+    }
+
+    // Score the kata, if it can be recommended
+    fn recommendation(&self, kata: &CodeChallenge) -> Option<Recommendation> {
+        if self.completed.contains(&kata.id) {
+            return None;
+        }
+        let kata_rank = kata.rank.as_ref()?;
+        // Find language with the best fitting rank
+        let (language, user_rank, difference, fit) = kata
+            .languages
+            .iter()
+            .filter_map(|language| {
+                let rank = self.languages.get(language)?;
+                let difference = level(kata_rank.id) - level(rank.rank);
+                Some((language, rank, difference, rank_fit(difference)))
+            })
+            .filter(|(_, _, _, fit)| *fit > 0.0)
+            .max_by(|a, b| a.3.total_cmp(&b.3).then(b.0.cmp(a.0)))?;
+        let underrepresented = self.underrepresented_tags(&kata.tags);
+        let tags_score = match kata.tags.is_empty() {
+            true => 0.0,
+            false => underrepresented.len() as f64 / kata.tags.len() as f64,
+        };
+        let mut reasons = vec![Reason::RankFit {
+            language: language.clone(),
+            kata_rank: kata_rank.name.clone(),
+            user_rank: user_rank.name.clone(),
+            difference,
+        }];
+        if !underrepresented.is_empty() {
+            reasons.push(Reason::UnderrepresentedTags {
+                tags: underrepresented,
+            });
+        }
+        Some(Recommendation {
+            id: kata.id.clone(),
+            name: kata.name.clone(),
+            url: kata.url.clone(),
+            language: language.clone(),
+            score: RANK_WEIGHT * fit + TAGS_WEIGHT * tags_score,
+            reasons,
+        })
+    }
+
+    // Get tags which were completed less than half of the average number of times per tag
+    fn underrepresented_tags(&self, tags: &[String]) -> Vec<String> {
+        let average = match self.tags.is_empty() {
+            true => 0.0,
+            false => self.tags.values().sum::<u64>() as f64 / self.tags.len() as f64,
+        };
+        tags.iter()
+            .filter(|tag| {
+                let count = self.tags.get(*tag).copied().unwrap_or(0);
+                count == 0 || (count as f64) < average / 2.0
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+// Convert rank to continuous level, from 0 for 8 kyu to 15 for 8 dan
+fn level(rank: i8) -> i8 {
+    if rank < 0 {
+        rank + 8
+    } else {
+        rank + 7
+    }
+}
+
+// Get fit of the kata rank by its difference from the user rank. Slightly harder katas fit best
+fn rank_fit(difference: i8) -> f64 {
+    match difference {
+        0 | 1 => 1.0,
+        -1 | 2 => 0.5,
+        _ => 0.0,
+    }
+}
+
+/// Format reason as human-readable explanation
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::RankFit {
+                language,
+                kata_rank,
+                user_rank,
+                difference,
+            } => {
+                let position = match difference {
+                    0 => "matches".to_string(),
+                    1 => "is 1 rank above".to_string(),
+                    -1 => "is 1 rank below".to_string(),
+                    difference if *difference > 0 => format!("is {} ranks above", difference),
+                    difference => format!("is {} ranks below", -difference),
+                };
+                write!(
+                    f,
+                    "{} {} your {} rank in {}",
+                    kata_rank, position, user_rank, language
+                )
+            }
+            Reason::UnderrepresentedTags { tags } => {
+                write!(f, "practices rarely solved tags: {}", tags.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for recommendations
+
+    use super::*;
+    use crate::fixtures::{self, kata_with as kata};
+
+    /// Test ranking and explaining candidates
+    #[test]
+    fn test_recommend() {
+        // Ranks of the user are 4 kyu in ruby and 3 kyu in javascript and coffeescript
+        let user = fixtures::user();
+        let completed = fixtures::completed_ids(&["a", "b", "c"]);
+        let katas: HashMap<String, CodeChallenge> = [
+            kata("a", Some(-4), &["ruby"], &["Strings", "Algorithms"]),
+            kata("b", Some(-4), &["ruby"], &["Strings", "Algorithms"]),
+            kata("c", Some(-4), &["ruby"], &["Strings", "Algorithms"]),
+        ]
+        .into_iter()
+        .map(|kata| (kata.id.clone(), kata))
+        .collect();
+        let candidates = [
+            kata("a", Some(-3), &["ruby"], &["Graphs"]),
+            kata("strings", Some(-3), &["ruby"], &["Strings"]),
+            kata("graphs", Some(-2), &["python", "javascript"], &["Graphs"]),
+            kata("easy", Some(-8), &["ruby"], &["Graphs"]),
+            kata("python", Some(-3), &["python"], &["Graphs"]),
+        ];
+        let recommender = Recommender::new(&user, &completed, &katas);
+        let recommendations = recommender.recommend(&candidates, 10);
+        assert_eq!(
+            recommendations
+                .iter()
+                .map(|recommendation| recommendation.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["graphs", "strings"]
+        );
+        let graphs = &recommendations[0];
+        assert_eq!(graphs.language, "javascript");
+        assert!((graphs.score - 1.0).abs() < f64::EPSILON);
+        assert_eq!(
+            graphs
+                .reasons
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "2 kyu is 1 rank above your 3 kyu rank in javascript",
+                "practices rarely solved tags: Graphs"
+            ]
+        );
+        assert_eq!(recommendations[1].reasons.len(), 1);
+        assert_eq!(recommender.recommend(&candidates, 1).len(), 1);
+    }
+}
@@ -1,4 +1,6 @@
 //! A module for interacting with the Codewars REST API
 
+pub mod api;
 pub mod client;
+pub mod fake;
 pub mod models;
@@ -0,0 +1,127 @@
+//! Trait over the Codewars REST API
+//!
+//! Write code against [`CodewarsApi`] instead of [`RestCodewarsClient`] to swap the backend,
+//! e.g. for [`FakeCodewarsApi`](crate::rest_api::fake::FakeCodewarsApi) in tests.
+
+use crate::rest_api::client::{ErrorKind, RestCodewarsClient};
+use crate::rest_api::models::{
+    AuthoredChallenges, CodeChallenge, CompletedChallenge, CompletedChallenges, User,
+};
+use async_trait::async_trait;
+
+/// Source of Codewars data
+///
+/// Implementations return errors in the same format as [`RestCodewarsClient`], e.g.
+/// `unexpected status code: 404 Not Found` for missing users and katas
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::rest_api::api::CodewarsApi;
+/// use codewars_api::RestCodewarsClient;
+///
+/// // Works with any backend
+/// async fn honor(api: &dyn CodewarsApi, username: &str) -> Result<u64, String> {
+///     Ok(api.get_user(username).await?.honor)
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// println!("Honor: {}", honor(&client, "ANKDDEV").await.unwrap());
+/// # }
+/// ```
+#[async_trait]
+pub trait CodewarsApi: Send + Sync {
+    /// Get info about user by username
+    async fn get_user(&self, username: &str) -> Result<User, String>;
+
+    /// Get info about kata by ID or slug
+    async fn get_kata(&self, slug: &str) -> Result<CodeChallenge, String>;
+
+    /// Get page of completed challenges, starting from 0
+    async fn get_completed_challenges(
+        &self,
+        username: &str,
+        page: u16,
+    ) -> Result<CompletedChallenges, String>;
+
+    /// Get list of authored challenges
+    async fn get_authored_challenges(&self, username: &str) -> Result<AuthoredChallenges, String>;
+
+    /// Get all completed challenges from all pages
+    async fn get_all_completed_challenges(
+        &self,
+        username: &str,
+    ) -> Result<Vec<CompletedChallenge>, String> {
+        // Get first page to know number of pages
+        let first_page = self.get_completed_challenges(username, 0).await?;
+        let mut challenges = first_page.data;
+        for page in 1..first_page.total_pages {
+            let page = u16::try_from(page).map_err(|err| err.to_string())?;
+            challenges.extend(self.get_completed_challenges(username, page).await?.data);
+        }
+        Ok(challenges)
+    }
+}
+
+/// Get info about kata, or `None` if it was deleted from Codewars
+///
+/// Completions and authored katas keep referencing deleted katas, for which `get_kata` returns
+/// `404 Not Found`
+pub(crate) async fn get_kata_if_exists(
+    api: &dyn CodewarsApi,
+    id: &str,
+) -> Result<Option<CodeChallenge>, String> {
+    match api.get_kata(id).await {
+        Ok(kata) => Ok(Some(kata)),
+        Err(err) if ErrorKind::of(&err) == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Implementation of CodewarsApi for RestCodewarsClient
+#[async_trait]
+impl CodewarsApi for RestCodewarsClient {
+    async fn get_user(&self, username: &str) -> Result<User, String> {
+        RestCodewarsClient::get_user(self, username).await
+    }
+
+    async fn get_kata(&self, slug: &str) -> Result<CodeChallenge, String> {
+        RestCodewarsClient::get_kata(self, slug).await
+    }
+
+    async fn get_completed_challenges(
+        &self,
+        username: &str,
+        page: u16,
+    ) -> Result<CompletedChallenges, String> {
+        RestCodewarsClient::get_completed_challenges(self, username, page).await
+    }
+
+    async fn get_authored_challenges(&self, username: &str) -> Result<AuthoredChallenges, String> {
+        RestCodewarsClient::get_authored_challenges(self, username).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for CodewarsApi trait
+
+    use super::*;
+    use crate::fixtures;
+
+    /// Test that REST client is usable through the trait
+    #[tokio::test]
+    async fn test_rest_client() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        let api: &dyn CodewarsApi = &client;
+        assert_eq!(api.get_user("some_user").await.unwrap().honor, 544);
+    }
+}
@@ -1,10 +1,13 @@
 //! Client for interacting with the Codewars REST API
 
 use std::string::ToString;
-use crate::rest_api::models::{AuthoredChallenges, CodeChallenge, CompletedChallenges, User};
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{
+    AuthoredChallenges, CodeChallenge, CompletedChallenge, CompletedChallenges, User,
+};
 
 /// Client for interacting with the Codewars API
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct RestCodewarsClient {
     host_name: String
 }
@@ -40,6 +43,23 @@ impl RestCodewarsClient {
         }
     }
 
+    /// Create new instance of RestCodewarsClient that sends requests to another server,
+    /// e.g. a proxy or a fake server in tests
+    ///
+    /// # Arguments:
+    /// * base_url (impl Into<String>) - URL of the server without trailing slash, e.g. `http://127.0.0.1:8080`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codewars_api::rest_api::client::RestCodewarsClient;
+    ///
+    /// let client = RestCodewarsClient::with_base_url("http://127.0.0.1:8080");
+    /// ```
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self::new_with_custom_host(base_url.into().trim_end_matches('/').to_string())
+    }
+
     /// Get info about user by username
     ///
     /// # Arguments:
@@ -50,6 +70,7 @@ impl RestCodewarsClient {
     ///
     /// # Errors:
     /// * `unexpected status code: {status_code}` - If the status code is not 200
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
     /// * `error decoding response body` - If there is an error decoding the response body with serde
     ///
     /// # Examples
@@ -72,7 +93,7 @@ impl RestCodewarsClient {
             self.host_name, username
         ))
         .await
-        .unwrap();
+        .map_err(|err| err.to_string())?;
         // Check status code
         match response.status() {
             reqwest::StatusCode::OK => match response.json::<User>().await {
@@ -96,6 +117,7 @@ impl RestCodewarsClient {
     ///
     /// # Errors:
     /// * `unexpected status code: {status_code}` - If the status code is not 200
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
     /// * `error decoding response body` - If there is an error decoding the response body with serde
     ///
     /// # Examples
@@ -118,7 +140,7 @@ impl RestCodewarsClient {
             self.host_name, slug
         ))
         .await
-        .unwrap();
+        .map_err(|err| err.to_string())?;
         // Check status code
         match response.status() {
             reqwest::StatusCode::OK => match response.json::<CodeChallenge>().await {
@@ -143,7 +165,8 @@ impl RestCodewarsClient {
     ///
     /// # Errors:
     /// * `unexpected status code: {status_code}` - If the status code is not 200
-    /// * `error decoding response body` - If there is an error decoding the response body with serde///
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
+    /// * `error decoding response body` - If there is an error decoding the response body with serde
     ///
     /// # Examples
     /// ```no_run
@@ -169,7 +192,7 @@ impl RestCodewarsClient {
             self.host_name, username, page
         ))
         .await
-        .unwrap();
+        .map_err(|err| err.to_string())?;
         // Check status code
         match response.status() {
             reqwest::StatusCode::OK => match response.json::<CompletedChallenges>().await {
@@ -193,6 +216,7 @@ impl RestCodewarsClient {
     ///
     /// # Errors:
     /// * `unexpected status code: {status_code}` - If the status code is not 200
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
     /// * `error decoding response body` - If there is an error decoding the response body with serde
     ///
     /// # Examples
@@ -216,6 +240,38 @@ impl RestCodewarsClient {
         self.get_completed_challenges(username, 0).await
     }
 
+    /// Get all completed challenges from all pages
+    ///
+    /// # Arguments:
+    /// * username (&str) - username of the user
+    ///
+    /// # Returns:
+    /// * Result<Vec<CompletedChallenge>, String> - Result that contains all completed challenges or an error message
+    ///
+    /// # Errors:
+    /// * `unexpected status code: {status_code}` - If the status code of any page is not 200
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
+    /// * `error decoding response body` - If there is an error decoding the response body with serde
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use codewars_api::rest_api::client::RestCodewarsClient;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let client = RestCodewarsClient::new();
+    /// let challenges = client.get_all_completed_challenges("ANKDDEV").await.unwrap();
+    /// // Get total number of completed challenges
+    /// println!("Total completed: {}", challenges.len());
+    /// # }
+    /// ```
+    pub async fn get_all_completed_challenges(
+        &self,
+        username: &str,
+    ) -> Result<Vec<CompletedChallenge>, String> {
+        // Paging is shared with other implementations of the API
+        <Self as CodewarsApi>::get_all_completed_challenges(self, username).await
+    }
+
     /// Get list of authored challenges
     ///
     /// # Arguments:
@@ -226,6 +282,7 @@ impl RestCodewarsClient {
     ///
     /// # Errors:
     /// * `unexpected status code: {status_code}` - If the status code is not 200
+    /// * `error sending request` - If the request can't be sent, e.g. because of network error
     /// * `error decoding response body` - If there is an error decoding the response body with serde
     ///
     /// # Examples
@@ -260,7 +317,7 @@ impl RestCodewarsClient {
             self.host_name, username
         ))
         .await
-        .unwrap();
+        .map_err(|err| err.to_string())?;
         // Check status code
         match response.status() {
             reqwest::StatusCode::OK => match response.json::<AuthoredChallenges>().await {
@@ -283,6 +340,57 @@ impl Default for RestCodewarsClient {
     }
 }
 
+/// Kind of error returned by RestCodewarsClient
+///
+/// Errors of the client are messages, so use this to handle them without matching the text
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::rest_api::client::{ErrorKind, RestCodewarsClient};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// match client.get_user("nobody").await {
+///     Ok(user) => println!("Honor: {}", user.honor),
+///     Err(err) if ErrorKind::of(&err) == ErrorKind::NotFound => println!("No such user"),
+///     Err(err) => println!("Error: {}", err),
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// User or kata doesn't exist, status code 404
+    NotFound,
+    /// Too many requests, status code 429
+    RateLimited,
+    /// Codewars is unavailable, status code 5xx, or the request can't be sent
+    Unavailable,
+    /// Response body can't be decoded
+    InvalidResponse,
+    /// Any other error
+    Other,
+}
+
+/// Implementation of ErrorKind
+impl ErrorKind {
+    /// Get kind of error message returned by the client
+    ///
+    /// # Arguments:
+    /// * err (&str) - error message
+    pub fn of(err: &str) -> Self {
+        match err.strip_prefix("unexpected status code: ") {
+            Some(status) if status.starts_with("404") => Self::NotFound,
+            Some(status) if status.starts_with("429") => Self::RateLimited,
+            Some(status) if status.starts_with('5') => Self::Unavailable,
+            Some(_) => Self::Other,
+            None if err.starts_with("error sending request") => Self::Unavailable,
+            None if err.starts_with("error decoding response body") => Self::InvalidResponse,
+            None => Self::Other,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! Tests for REST Client
@@ -333,6 +441,27 @@ mod tests {
         assert_eq!(result, text);
     }
 
+    /// Test getting completed challenges from all pages
+    #[tokio::test]
+    async fn test_get_all_completed_challenges() {
+        let mut server = mockito::Server::new_async().await;
+        let host = server.host_with_port();
+        let client = RestCodewarsClient::new_with_custom_host(format!("http://{}", host));
+        let content = std::fs::read_to_string(Path::new(&"tests/mocks/get_completed_challenges.json")).unwrap();
+        let mut page: CompletedChallenges = serde_json::from_str(&content).unwrap();
+        page.total_pages = 2;
+        let mut mocks = Vec::new();
+        for number in 0..2 {
+            page.data[0].id = format!("kata_{}", number);
+            mocks.push(server.mock("GET", format!("/api/v1/users/some_user/code-challenges/completed?page={}", number).as_str()).with_status(200).with_header("content-type", "application/json").with_body(serde_json::to_string(&page).unwrap()).create_async().await);
+        }
+        let result = client.get_all_completed_challenges("some_user").await.unwrap();
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(result.iter().map(|kata| kata.id.as_str()).collect::<Vec<_>>(), vec!["kata_0", "kata_1"]);
+    }
+
     /// Test getting authored challenges
     #[tokio::test]
     async fn test_get_authored_challenges() {
@@ -345,6 +474,7 @@ mod tests {
         let result = client.get_authored_challenges("some_user").await.unwrap();
         mock.assert_async().await;
         assert_eq!(result, text);
+        assert_eq!(result.data[0].rank, Some(-3));
     }
 
     /// Test getting code challenge information
@@ -359,5 +489,27 @@ mod tests {
         let result = client.get_kata(&text.slug).await.unwrap();
         mock.assert_async().await;
         assert_eq!(result, text);
+        assert_eq!(result.rank.unwrap().name, "4 kyu");
+        assert_eq!(result.approved_by.unwrap().username, "xDranik");
+    }
+
+    /// Test getting kind of errors
+    #[tokio::test]
+    async fn test_error_kind() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        for (status, kind) in [(404, ErrorKind::NotFound), (429, ErrorKind::RateLimited), (503, ErrorKind::Unavailable), (400, ErrorKind::Other)] {
+            let mock = server.mock("GET", "/api/v1/users/some_user").with_status(status).create_async().await;
+            let err = client.get_user("some_user").await.unwrap_err();
+            assert_eq!(ErrorKind::of(&err), kind);
+            mock.remove_async().await;
+        }
+        let mock = server.mock("GET", "/api/v1/users/some_user").with_status(200).with_body("{}").create_async().await;
+        let err = client.get_user("some_user").await.unwrap_err();
+        assert_eq!(ErrorKind::of(&err), ErrorKind::InvalidResponse);
+        mock.remove_async().await;
+        let client = RestCodewarsClient::new_with_custom_host("http://127.0.0.1:1".to_string());
+        let err = client.get_user("some_user").await.unwrap_err();
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Unavailable);
     }
 }
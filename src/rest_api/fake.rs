@@ -0,0 +1,220 @@
+//! In-memory implementation of [`CodewarsApi`] for tests
+//!
+//! [`FakeCodewarsApi`] serves users, katas and completions added to it without any HTTP
+//! requests. Missing data is reported with the same errors as [`RestCodewarsClient`] returns.
+//!
+//! [`RestCodewarsClient`]: crate::rest_api::client::RestCodewarsClient
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{
+    AuthoredChallenge, AuthoredChallenges, CodeChallenge, CompletedChallenge, CompletedChallenges,
+    User,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Number of completed challenges on a page, as Codewars returns them
+pub const PAGE_SIZE: usize = 200;
+
+/// Error returned for missing users and katas
+const NOT_FOUND: &str = "unexpected status code: 404 Not Found";
+
+/// Fixtures the fake can be seeded with, from Codewars documentation
+pub(crate) const USER_FIXTURE: &str = include_str!("../../tests/mocks/get_user.json");
+pub(crate) const KATA_FIXTURE: &str = include_str!("../../tests/mocks/get_challenge.json");
+pub(crate) const COMPLETED_FIXTURE: &str =
+    include_str!("../../tests/mocks/get_completed_challenges.json");
+pub(crate) const AUTHORED_FIXTURE: &str =
+    include_str!("../../tests/mocks/get_authored_challenges.json");
+
+/// In-memory Codewars API
+#[derive(Debug, Clone, Default)]
+pub struct FakeCodewarsApi {
+    users: HashMap<String, User>,
+    katas: Vec<CodeChallenge>,
+    completed: HashMap<String, Vec<CompletedChallenge>>,
+    authored: HashMap<String, Vec<AuthoredChallenge>>,
+}
+
+/// Implementation of FakeCodewarsApi
+impl FakeCodewarsApi {
+    /// Create empty fake API
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create fake API seeded with fixtures from Codewars documentation
+    ///
+    /// It contains user `some_user` with 1 completed and 2 authored katas, and kata
+    /// `valid-braces`
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::rest_api::api::CodewarsApi;
+    /// use codewars_api::rest_api::fake::FakeCodewarsApi;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api = FakeCodewarsApi::with_fixtures();
+    /// let user = api.get_user("some_user").await.unwrap();
+    /// assert_eq!(user.honor, 544);
+    /// assert!(api.get_user("nobody").await.is_err());
+    /// # }
+    /// ```
+    pub fn with_fixtures() -> Self {
+        // Fixtures are checked by tests, so parsing them can't fail
+        let user: User = serde_json::from_str(USER_FIXTURE).unwrap();
+        let completed: CompletedChallenges = serde_json::from_str(COMPLETED_FIXTURE).unwrap();
+        let authored: AuthoredChallenges = serde_json::from_str(AUTHORED_FIXTURE).unwrap();
+        let username = user.username.clone();
+        Self::new()
+            .with_user(user)
+            .with_kata(serde_json::from_str(KATA_FIXTURE).unwrap())
+            .with_completed(&username, completed.data)
+            .with_authored(&username, authored.data)
+    }
+
+    /// Add user, replacing the user with the same username
+    pub fn with_user(mut self, user: User) -> Self {
+        self.users.insert(user.username.clone(), user);
+        self
+    }
+
+    /// Add kata, which can be fetched by ID or slug
+    pub fn with_kata(mut self, kata: CodeChallenge) -> Self {
+        self.katas.retain(|other| other.id != kata.id);
+        self.katas.push(kata);
+        self
+    }
+
+    /// Add completed challenges of the user, most recent first
+    ///
+    /// Challenges are split to pages of [`PAGE_SIZE`]
+    pub fn with_completed(mut self, username: &str, completed: Vec<CompletedChallenge>) -> Self {
+        self.completed
+            .entry(username.to_string())
+            .or_default()
+            .extend(completed);
+        self
+    }
+
+    /// Add authored challenges of the user
+    pub fn with_authored(mut self, username: &str, authored: Vec<AuthoredChallenge>) -> Self {
+        self.authored
+            .entry(username.to_string())
+            .or_default()
+            .extend(authored);
+        self
+    }
+
+    // Return error if there is no such user
+    fn check_user(&self, username: &str) -> Result<(), String> {
+        match self.users.contains_key(username) {
+            true => Ok(()),
+            false => Err(NOT_FOUND.to_string()),
+        }
+    }
+}
+
+/// Implementation of CodewarsApi for FakeCodewarsApi
+#[async_trait]
+impl CodewarsApi for FakeCodewarsApi {
+    async fn get_user(&self, username: &str) -> Result<User, String> {
+        self.users
+            .get(username)
+            .cloned()
+            .ok_or(NOT_FOUND.to_string())
+    }
+
+    async fn get_kata(&self, slug: &str) -> Result<CodeChallenge, String> {
+        self.katas
+            .iter()
+            .find(|kata| kata.id == slug || kata.slug == slug)
+            .cloned()
+            .ok_or(NOT_FOUND.to_string())
+    }
+
+    async fn get_completed_challenges(
+        &self,
+        username: &str,
+        page: u16,
+    ) -> Result<CompletedChallenges, String> {
+        self.check_user(username)?;
+        let completed = self.completed.get(username).map_or(&[][..], Vec::as_slice);
+        Ok(CompletedChallenges {
+            total_pages: completed.len().div_ceil(PAGE_SIZE) as u64,
+            total_items: completed.len() as u64,
+            data: completed
+                .chunks(PAGE_SIZE)
+                .nth(page as usize)
+                .unwrap_or_default()
+                .to_vec(),
+        })
+    }
+
+    async fn get_authored_challenges(&self, username: &str) -> Result<AuthoredChallenges, String> {
+        self.check_user(username)?;
+        Ok(AuthoredChallenges {
+            data: self.authored.get(username).cloned().unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for fake Codewars API
+
+    use super::*;
+
+    /// Test serving fixtures
+    #[tokio::test]
+    async fn test_fixtures() {
+        let api = FakeCodewarsApi::with_fixtures();
+        let kata = api.get_kata("valid-braces").await.unwrap();
+        assert_eq!(api.get_kata(&kata.id).await, Ok(kata));
+        assert_eq!(
+            api.get_authored_challenges("some_user")
+                .await
+                .unwrap()
+                .data
+                .len(),
+            2
+        );
+        assert_eq!(
+            api.get_kata("missing").await,
+            Err("unexpected status code: 404 Not Found".to_string())
+        );
+        assert_eq!(
+            api.get_completed_challenges("nobody", 0).await,
+            Err("unexpected status code: 404 Not Found".to_string())
+        );
+    }
+
+    /// Test pagination of completed challenges
+    #[tokio::test]
+    async fn test_pagination() {
+        let completed: Vec<CompletedChallenge> = (0..450)
+            .map(|index| CompletedChallenge {
+                id: index.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let api = FakeCodewarsApi::with_fixtures().with_completed("other", completed.clone());
+        assert!(api.get_completed_challenges("other", 0).await.is_err());
+        let user = api.get_user("some_user").await.unwrap();
+        let api = api.with_user(User {
+            username: "other".to_string(),
+            ..user
+        });
+        let page = api.get_completed_challenges("other", 2).await.unwrap();
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(page.total_items, 450);
+        assert_eq!(page.data.len(), 50);
+        assert_eq!(
+            api.get_all_completed_challenges("other").await,
+            Ok(completed)
+        );
+        let page = api.get_completed_challenges("other", 3).await.unwrap();
+        assert!(page.data.is_empty());
+    }
+}
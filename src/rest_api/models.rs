@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents a Codewars user
 ///
@@ -67,6 +69,45 @@ pub struct Rank {
     pub score: u64,
 }
 
+/// Implementation of Rank
+impl Rank {
+    /// Create rank with the name and color matching rank value, e.g. for ranks from webhooks
+    ///
+    /// # Arguments:
+    /// * rank (i8) - rank in integer. [-8, -1] maps to kyu, [1, 8] maps to dan
+    /// * score (u64) - total score earned
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::rest_api::models::{Color, Rank};
+    ///
+    /// let rank = Rank::from_value(-2, 4900);
+    /// assert_eq!(rank.name, "2 kyu");
+    /// assert_eq!(rank.color, Color::Purple);
+    /// ```
+    pub fn from_value(rank: i8, score: u64) -> Self {
+        let name = if rank < 0 {
+            format!("{} kyu", -rank)
+        } else {
+            format!("{} dan", rank)
+        };
+        let color = match rank {
+            -8..=-7 => Color::White,
+            -6..=-5 => Color::Yellow,
+            -4..=-3 => Color::Blue,
+            -2..=-1 => Color::Purple,
+            1..=4 => Color::Black,
+            _ => Color::Red,
+        };
+        Self {
+            rank,
+            name,
+            color,
+            score,
+        }
+    }
+}
+
 /// Represents a color of the rank
 ///
 /// Read more at [Codewars documentation](https://dev.codewars.com/#get-user)
@@ -119,10 +160,10 @@ pub struct AuthoredChallenge {
     /// Description of the kata in Markdown
     pub description: String,
     /// Rank of the kata if approved
-    #[serde(flatten)]
+    #[serde(default)]
     pub rank: Option<i8>,
     /// Rank name of the kata if approved
-    #[serde(rename = "rankName", flatten)]
+    #[serde(rename = "rankName", default)]
     pub rank_name: Option<String>,
     /// Array of tags associated with the kata
     pub tags: Vec<String>,
@@ -161,8 +202,8 @@ pub struct CodeChallenge {
     /// Array of language names the kata is available in
     pub languages: Vec<String>,
     /// Object describing the rank of the kata if approved
-    #[serde(flatten)]
-    pub rank: Option<Rank>,
+    #[serde(default, deserialize_with = "deserialize_kata_rank")]
+    pub rank: Option<KataRank>,
     /// The author of the kata
     #[serde(rename = "createdBy")]
     pub created_by: Author,
@@ -170,11 +211,11 @@ pub struct CodeChallenge {
     #[serde(rename = "publishedAt")]
     pub published_at: String,
     /// The approver of the kata
-    #[serde(rename = "approvedBy", flatten)]
+    #[serde(rename = "approvedBy", default)]
     pub approved_by: Option<Author>,
     /// Date and time when the kata was approved
-    #[serde(rename = "approvedAt")]
-    pub approved_at: String,
+    #[serde(rename = "approvedAt", default)]
+    pub approved_at: Option<String>,
     /// Total number of completions
     #[serde(rename = "totalCompleted")]
     pub total_completed: u64,
@@ -194,6 +235,42 @@ pub struct CodeChallenge {
     pub unresolved: Unresolved,
 }
 
+/// Represents a rank of code challenge
+///
+/// Read more at [Codewars documentation](https://dev.codewars.com/#get-code-challenge)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct KataRank {
+    /// Rank in integer. [-8, -1] maps to kyu, [1, 8] maps to dan
+    pub id: i8,
+    /// Either {-rank} kyu or {rank} dan
+    pub name: String,
+    /// The color of the rank
+    pub color: Color,
+}
+
+/// Rank object as it is sent by Codewars. Katas in beta have rank with null fields
+#[derive(Deserialize)]
+struct RawKataRank {
+    id: Option<i8>,
+    name: Option<String>,
+    color: Option<Color>,
+}
+
+/// Deserialize rank of code challenge, treating rank with null fields as no rank
+fn deserialize_kata_rank<'de, D>(deserializer: D) -> Result<Option<KataRank>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Option<RawKataRank> = Option::deserialize(deserializer)?;
+    Ok(raw.and_then(|raw| {
+        Some(KataRank {
+            id: raw.id?,
+            name: raw.name?,
+            color: raw.color?,
+        })
+    }))
+}
+
 /// Represents an author of code challenge
 ///
 /// Read more at [Codewars documentation](https://dev.codewars.com/#get-code-challenge)
@@ -249,3 +326,97 @@ pub struct CompletedChallenge {
     #[serde(rename = "completedLanguages")]
     pub completed_languages: Vec<String>,
 }
+
+/// Implementation of CompletedChallenge
+impl CompletedChallenge {
+    /// Get sorted languages the kata was completed in, without duplicates
+    ///
+    /// Codewars repeats language for every solution in `completed_languages`
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::rest_api::models::CompletedChallenge;
+    ///
+    /// let kata = CompletedChallenge {
+    ///     completed_languages: vec!["ruby".to_string(), "go".to_string(), "ruby".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(kata.languages(), vec!["go", "ruby"]);
+    /// ```
+    pub fn languages(&self) -> Vec<String> {
+        let mut languages = self.completed_languages.clone();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+}
+
+/// Represents a reference to a kata by its ID or slug
+///
+/// Can be parsed from ID, slug or URL of the kata
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub enum KataRef {
+    /// ID of the kata, e.g. `5277c8a221e209d3f6000b56`
+    Id(String),
+    /// Slug of the kata, e.g. `valid-braces`
+    Slug(String),
+}
+
+/// Implementation of KataRef
+impl KataRef {
+    /// Get ID or slug of the kata, which can be passed to `get_kata`
+    pub fn as_str(&self) -> &str {
+        match self {
+            KataRef::Id(id) => id,
+            KataRef::Slug(slug) => slug,
+        }
+    }
+
+    /// Get URL of the kata on Codewars
+    pub fn url(&self) -> String {
+        format!("https://www.codewars.com/kata/{}", self.as_str())
+    }
+}
+
+/// Parse kata reference from ID, slug or URL
+///
+/// # Examples
+/// ```
+/// use codewars_api::rest_api::models::KataRef;
+///
+/// let kata: KataRef = "https://www.codewars.com/kata/valid-braces/train/rust".parse().unwrap();
+/// assert_eq!(kata, KataRef::Slug("valid-braces".to_string()));
+/// let kata: KataRef = "5277c8a221e209d3f6000b56".parse().unwrap();
+/// assert_eq!(kata, KataRef::Id("5277c8a221e209d3f6000b56".to_string()));
+/// ```
+impl FromStr for KataRef {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        // Take part of URL after `/kata/`, e.g. `https://www.codewars.com/kata/valid-braces/train/rust`
+        let reference = match value.split_once("/kata/") {
+            Some((_, path)) => path
+                .split(['/', '?', '#'])
+                .next()
+                .unwrap_or_default(),
+            None => value,
+        };
+        if reference.is_empty() || reference.contains(['/', ' ']) {
+            return Err(format!("invalid kata reference: {}", value));
+        }
+        // IDs of katas are MongoDB object IDs
+        if reference.len() == 24 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(KataRef::Id(reference.to_string()))
+        } else {
+            Ok(KataRef::Slug(reference.to_string()))
+        }
+    }
+}
+
+/// Format kata reference as ID or slug
+impl fmt::Display for KataRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
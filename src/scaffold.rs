@@ -0,0 +1,375 @@
+//! Local workspaces for solving katas
+//!
+//! A workspace is created in `<directory>/<slug>/<language>` and contains `README.md` with the
+//! description rendered for the language, `kata.json` with metadata of the kata and a stub
+//! project for the language.
+
+use crate::description::extract::{Document, Example};
+use crate::description::render::render_markdown;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{CodeChallenge, KataRef};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Keywords which can't be used as function names
+const RUST_KEYWORDS: [&str; 52] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Represents metadata of a kata written to `kata.json`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct KataMetadata {
+    /// ID of the kata
+    pub id: String,
+    /// Name of the kata
+    pub name: String,
+    /// Slug of the kata
+    pub slug: String,
+    /// URL of the kata
+    pub url: String,
+    /// Language of the workspace
+    pub language: String,
+    /// Rank name of the kata, if it is approved
+    pub rank: Option<String>,
+    /// Tags of the kata
+    pub tags: Vec<String>,
+}
+
+/// Implementation of KataMetadata
+impl KataMetadata {
+    /// Create metadata of the kata for the language
+    pub fn new(kata: &CodeChallenge, language: &str) -> Self {
+        Self {
+            id: kata.id.clone(),
+            name: kata.name.clone(),
+            slug: kata.slug.clone(),
+            url: kata.url.clone(),
+            language: language.to_string(),
+            rank: kata.rank.as_ref().map(|rank| rank.name.clone()),
+            tags: kata.tags.clone(),
+        }
+    }
+}
+
+/// Create workspace for the kata in the language
+///
+/// Stub projects are created for Rust, Python, JavaScript, TypeScript and Ruby, and a single
+/// solution file for other known languages. Examples from the description are added to tests
+/// as comments. The Rust test is ignored until the examples are filled in
+///
+/// # Arguments:
+/// * kata (&CodeChallenge) - kata to create workspace for
+/// * language (&str) - language to solve the kata in, e.g. `rust`
+/// * directory (impl AsRef<Path>) - directory to create workspace in
+///
+/// # Errors:
+/// * `kata is not available in {language}` - If the kata has no translation into the language
+/// * `workspace already exists: {path}` - If the workspace directory exists
+/// * Errors of writing files
+///
+/// Returns paths of created files
+pub fn create_workspace(
+    kata: &CodeChallenge,
+    language: &str,
+    directory: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, String> {
+    let language = language.to_lowercase();
+    if !kata.languages.contains(&language) {
+        return Err(format!("kata is not available in {}", language));
+    }
+    let workspace = directory.as_ref().join(&kata.slug).join(&language);
+    if workspace.exists() {
+        return Err(format!("workspace already exists: {}", workspace.display()));
+    }
+    let description = render_markdown(&kata.description, &language);
+    let examples = Document::parse(&description).examples;
+    let mut readme = format!("# {}\n\n", kata.name);
+    if let Some(rank) = &kata.rank {
+        readme.push_str(&format!("{} | ", rank.name));
+    }
+    readme.push_str(&format!("<{}>\n\n{}", kata.url, description));
+    let metadata = serde_json::to_string_pretty(&KataMetadata::new(kata, &language))
+        .map_err(|err| err.to_string())?;
+    let mut files = vec![
+        ("README.md".to_string(), readme),
+        ("kata.json".to_string(), metadata + "\n"),
+    ];
+    files.extend(stub_files(&kata.slug, &language, &examples));
+    let mut paths = Vec::new();
+    for (name, content) in files {
+        let path = workspace.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).map_err(|err| err.to_string())?;
+        std::fs::write(&path, content).map_err(|err| err.to_string())?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Fetch the kata and create workspace for it in the language
+///
+/// See [`create_workspace`] for created files
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * kata (&KataRef) - ID or slug of the kata
+/// * language (&str) - language to solve the kata in, e.g. `rust`
+/// * directory (impl AsRef<Path>) - directory to create workspace in
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::rest_api::models::KataRef;
+/// use codewars_api::scaffold::scaffold;
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let kata: KataRef = "valid-braces".parse().unwrap();
+/// let files = scaffold(&client, &kata, "rust", ".").await.unwrap();
+/// for file in files {
+///     println!("{}", file.display());
+/// }
+/// # }
+/// ```
+pub async fn scaffold(
+    client: &dyn CodewarsApi,
+    kata: &KataRef,
+    language: &str,
+    directory: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, String> {
+    let kata = client.get_kata(kata.as_str()).await?;
+    create_workspace(&kata, language, directory)
+}
+
+// Get paths and contents of stub files for the language
+fn stub_files(slug: &str, language: &str, examples: &[Example]) -> Vec<(String, String)> {
+    let comments = |prefix: &str| -> String {
+        examples
+            .iter()
+            .map(|example| {
+                format!(
+                    "{}{}({}) => {}\n",
+                    prefix, example.function, example.arguments, example.expected
+                )
+            })
+            .collect()
+    };
+    match language {
+        "rust" => {
+            let mut name = slug.replace('-', "_");
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                name.insert_str(0, "kata_");
+            }
+            vec![
+                (
+                    "Cargo.toml".to_string(),
+                    format!(
+                        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+                        name
+                    ),
+                ),
+                (
+                    "src/lib.rs".to_string(),
+                    format!(
+                        "{}\n#[cfg(test)]\nmod tests {{\n    #[allow(unused_imports)]\n    use super::*;\n\n    #[test]\n    #[ignore = \"fill in examples\"]\n    fn examples() {{\n{}    }}\n}}\n",
+                        rust_solution(examples),
+                        comments("        // ")
+                    ),
+                ),
+            ]
+        }
+        "python" => vec![
+            (
+                "solution.py".to_string(),
+                "# Write your solution here\n".to_string(),
+            ),
+            (
+                "test_solution.py".to_string(),
+                format!(
+                    "from solution import *\n\n\ndef test_examples():\n{}    pass\n",
+                    comments("    # ")
+                ),
+            ),
+        ],
+        "javascript" => vec![
+            (
+                "solution.js".to_string(),
+                "// Write your solution here\n\nmodule.exports = {};\n".to_string(),
+            ),
+            (
+                "solution.test.js".to_string(),
+                format!(
+                    "const solution = require(\"./solution\");\n\ntest(\"examples\", () => {{\n{}}});\n",
+                    comments("  // ")
+                ),
+            ),
+        ],
+        "typescript" => vec![
+            (
+                "solution.ts".to_string(),
+                "// Write your solution here\n\nexport {};\n".to_string(),
+            ),
+            (
+                "solution.test.ts".to_string(),
+                format!(
+                    "import * as solution from \"./solution\";\n\ntest(\"examples\", () => {{\n{}}});\n",
+                    comments("  // ")
+                ),
+            ),
+        ],
+        "ruby" => vec![
+            (
+                "solution.rb".to_string(),
+                "# Write your solution here\n".to_string(),
+            ),
+            (
+                "solution_spec.rb".to_string(),
+                format!(
+                    "require_relative \"solution\"\n\ndescribe \"examples\" do\n{}end\n",
+                    comments("  # ")
+                ),
+            ),
+        ],
+        language => match extension(language) {
+            Some(extension) => vec![(
+                format!("solution.{}", extension),
+                String::new(),
+            )],
+            None => Vec::new(),
+        },
+    }
+}
+
+// Get stub of the function called in examples, which compiles until it is called
+fn rust_solution(examples: &[Example]) -> String {
+    // Methods like `Kata.solve` are stubbed as plain functions
+    let function = examples
+        .first()
+        .and_then(|example| example.function.rsplit(['.', ':']).next());
+    match function {
+        Some(function) if is_rust_identifier(function) => format!(
+            "// Write your solution here, changing the signature to match the kata\npub fn {}() {{\n    todo!()\n}}\n",
+            function
+        ),
+        _ => "// Write your solution here\n".to_string(),
+    }
+}
+
+// Check that the name can be used as a Rust function name
+fn is_rust_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+        && !RUST_KEYWORDS.contains(&name)
+}
+
+// Get extension of source files of the language
+fn extension(language: &str) -> Option<&'static str> {
+    Some(match language {
+        "c" => "c",
+        "coffeescript" => "coffee",
+        "cpp" => "cpp",
+        "csharp" => "cs",
+        "dart" => "dart",
+        "elixir" => "ex",
+        "fsharp" => "fs",
+        "go" => "go",
+        "haskell" => "hs",
+        "java" => "java",
+        "kotlin" => "kt",
+        "lua" => "lua",
+        "php" => "php",
+        "scala" => "scala",
+        "shell" => "sh",
+        "sql" => "sql",
+        "swift" => "swift",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for kata workspaces
+
+    use super::*;
+
+    /// Test creating Rust workspace
+    #[test]
+    fn test_create_workspace() {
+        let kata = CodeChallenge {
+            id: "5277c8a221e209d3f6000b56".to_string(),
+            name: "Valid Braces".to_string(),
+            slug: "valid-braces".to_string(),
+            url: "https://www.codewars.com/kata/valid-braces".to_string(),
+            description: "Check braces.\n\n~~~if:rust\n`valid_braces(\"()\") => true`\n~~~\n~~~if:python\nPython note.\n~~~\n".to_string(),
+            languages: vec!["rust".to_string(), "python".to_string()],
+            tags: vec!["Algorithms".to_string()],
+            ..Default::default()
+        };
+        let directory = tempfile::tempdir().unwrap();
+        let files = create_workspace(&kata, "Rust", directory.path()).unwrap();
+        let workspace = directory.path().join("valid-braces").join("rust");
+        assert_eq!(
+            files,
+            ["README.md", "kata.json", "Cargo.toml", "src/lib.rs"]
+                .iter()
+                .map(|file| workspace.join(file))
+                .collect::<Vec<_>>()
+        );
+        let read = |file: &str| std::fs::read_to_string(workspace.join(file)).unwrap();
+        assert_eq!(
+            read("README.md"),
+            "# Valid Braces\n\n<https://www.codewars.com/kata/valid-braces>\n\n\
+             Check braces.\n\n`valid_braces(\"()\") => true`\n"
+        );
+        let metadata: KataMetadata = serde_json::from_str(&read("kata.json")).unwrap();
+        assert_eq!(metadata.language, "rust");
+        assert_eq!(metadata.rank, None);
+        assert!(read("Cargo.toml").contains("name = \"valid_braces\""));
+        assert_eq!(
+            read("src/lib.rs"),
+            "// Write your solution here, changing the signature to match the kata\n\
+             pub fn valid_braces() {\n    todo!()\n}\n\n\
+             #[cfg(test)]\nmod tests {\n    #[allow(unused_imports)]\n    use super::*;\n\n    \
+             #[test]\n    #[ignore = \"fill in examples\"]\n    fn examples() {\n        \
+             // valid_braces(\"()\") => true\n    }\n}\n"
+        );
+        assert_eq!(
+            create_workspace(&kata, "rust", directory.path()),
+            Err(format!("workspace already exists: {}", workspace.display()))
+        );
+        assert_eq!(
+            create_workspace(&kata, "go", directory.path()),
+            Err("kata is not available in go".to_string())
+        );
+    }
+
+    /// Test Rust stubs of called methods and names which aren't identifiers
+    #[test]
+    fn test_rust_solution() {
+        let example = |function: &str| Example {
+            function: function.to_string(),
+            arguments: "1".to_string(),
+            expected: "2".to_string(),
+        };
+        assert_eq!(
+            rust_solution(&[example("Kata.solve")]),
+            "// Write your solution here, changing the signature to match the kata\n\
+             pub fn solve() {\n    todo!()\n}\n"
+        );
+        for function in ["match", "Kata.type", "_", "2d", "часы"] {
+            assert_eq!(
+                rust_solution(&[example(function)]),
+                "// Write your solution here\n"
+            );
+        }
+        assert_eq!(rust_solution(&[]), "// Write your solution here\n");
+    }
+}
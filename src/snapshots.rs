@@ -0,0 +1,9 @@
+//! Store for historical snapshots of users
+//!
+//! Codewars API exposes only the current state of a user. Record snapshots regularly with
+//! [`store::record`] to query progress of the user over time.
+
+pub mod jsonl;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
@@ -0,0 +1,215 @@
+//! Snapshot store that keeps snapshots in a JSON Lines file
+
+use crate::rest_api::models::{CompletedChallenges, User};
+use crate::snapshots::store::{Snapshot, SnapshotStore};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Record in the file of [`JsonLinesStore`]
+#[derive(Serialize, Deserialize)]
+struct Record {
+    // Kind of snapshot, either `user` or `completed`
+    kind: String,
+    username: String,
+    taken_at: DateTime<Utc>,
+    data: serde_json::Value,
+}
+
+/// Snapshot store that appends every snapshot as a line to a JSON Lines file
+///
+/// File is read on every query, so this store is fine for a few users with daily snapshots.
+/// Use SQLite store from `sqlite` feature for larger histories.
+#[derive(Debug, Clone)]
+pub struct JsonLinesStore {
+    path: PathBuf,
+}
+
+/// Implementation of JsonLinesStore
+impl JsonLinesStore {
+    /// Create new store in the file. File is created on the first save
+    ///
+    /// # Arguments:
+    /// * path (impl AsRef<Path>) - path to the file
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    // Append record to the file
+    fn append(
+        &self,
+        kind: &str,
+        username: &str,
+        taken_at: DateTime<Utc>,
+        data: impl Serialize,
+    ) -> Result<(), String> {
+        let record = Record {
+            kind: kind.to_string(),
+            username: username.to_string(),
+            taken_at,
+            data: serde_json::to_value(data).map_err(|err| err.to_string())?,
+        };
+        let mut line = serde_json::to_string(&record).map_err(|err| err.to_string())?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|err| err.to_string())
+    }
+
+    // Read records of the kind for the user taken in the time range
+    fn read<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<T>>, String> {
+        let file = match OpenOptions::new().read(true).open(&self.path) {
+            Ok(file) => file,
+            // Nothing was saved yet
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.to_string()),
+        };
+        let mut result = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|err| err.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line).map_err(|err| err.to_string())?;
+            if record.kind == kind
+                && record.username == username
+                && record.taken_at >= from
+                && record.taken_at < to
+            {
+                result.push(Snapshot {
+                    taken_at: record.taken_at,
+                    data: serde_json::from_value(record.data).map_err(|err| err.to_string())?,
+                });
+            }
+        }
+        result.sort_by_key(|snapshot| snapshot.taken_at);
+        Ok(result)
+    }
+}
+
+impl SnapshotStore for JsonLinesStore {
+    fn save_user(&mut self, snapshot: &Snapshot<User>) -> Result<(), String> {
+        self.append(
+            "user",
+            &snapshot.data.username,
+            snapshot.taken_at,
+            &snapshot.data,
+        )
+    }
+
+    fn save_completed(
+        &mut self,
+        username: &str,
+        snapshot: &Snapshot<CompletedChallenges>,
+    ) -> Result<(), String> {
+        self.append("completed", username, snapshot.taken_at, &snapshot.data)
+    }
+
+    fn users(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<User>>, String> {
+        self.read("user", username, from, to)
+    }
+
+    fn completed(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<CompletedChallenges>>, String> {
+        self.read("completed", username, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for JSON Lines snapshot store
+
+    use super::*;
+    use crate::fixtures::{self, user};
+    use crate::snapshots::store::Point;
+    use chrono::Duration;
+
+    /// Test querying history of honor and language score
+    #[test]
+    fn test_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JsonLinesStore::new(dir.path().join("snapshots.jsonl"));
+        let now = Utc::now();
+        for days in [100, 30, 1] {
+            let mut data = user();
+            data.honor -= days;
+            data.ranks.languages.get_mut("ruby").unwrap().score -= days;
+            store
+                .save_user(&Snapshot {
+                    taken_at: now - Duration::days(days as i64),
+                    data,
+                })
+                .unwrap();
+        }
+        let from = now - Duration::days(90);
+        assert_eq!(
+            store.honor_history("some_user", from, now).unwrap(),
+            vec![
+                Point {
+                    taken_at: now - Duration::days(30),
+                    value: 514
+                },
+                Point {
+                    taken_at: now - Duration::days(1),
+                    value: 543
+                },
+            ]
+        );
+        let scores = store
+            .language_score_history("some_user", "ruby", from, now)
+            .unwrap();
+        assert_eq!(
+            scores.iter().map(|point| point.value).collect::<Vec<_>>(),
+            vec![975, 1004]
+        );
+        assert!(store
+            .language_score_history("some_user", "rust", from, now)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            store.latest_user("some_user").unwrap().unwrap().data.honor,
+            543
+        );
+        assert_eq!(store.latest_user("other_user").unwrap(), None);
+    }
+
+    /// Test saving and reading completed challenges
+    #[test]
+    fn test_completed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JsonLinesStore::new(dir.path().join("snapshots.jsonl"));
+        let snapshot = Snapshot {
+            taken_at: Utc::now(),
+            data: fixtures::completed(),
+        };
+        store.save_completed("some_user", &snapshot).unwrap();
+        let range = (snapshot.taken_at, snapshot.taken_at + Duration::seconds(1));
+        assert_eq!(
+            store.completed("some_user", range.0, range.1).unwrap(),
+            vec![snapshot]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Snapshot store that keeps snapshots in a SQLite database
+//!
+//! Available with `sqlite` feature
+
+use crate::rest_api::models::{CompletedChallenges, User};
+use crate::snapshots::store::{Snapshot, SnapshotStore};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// Snapshot store that keeps snapshots in `snapshots` table of a SQLite database
+///
+/// Data of snapshots is stored as JSON, so it can be queried with SQLite JSON functions.
+/// Times are stored in RFC 3339 format in UTC, so they can be compared as strings.
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+/// Implementation of SqliteStore
+impl SqliteStore {
+    /// Open store in the database file, creating the file and the table if they don't exist
+    ///
+    /// # Arguments:
+    /// * path (impl AsRef<Path>) - path to the database file
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::snapshots::sqlite::SqliteStore;
+    ///
+    /// let store = SqliteStore::open("snapshots.db").unwrap();
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::init(Connection::open(path).map_err(|err| err.to_string())?)
+    }
+
+    /// Open store in a database in memory
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::init(Connection::open_in_memory().map_err(|err| err.to_string())?)
+    }
+
+    // Create table if it doesn't exist
+    fn init(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS snapshots (
+                    id INTEGER PRIMARY KEY,
+                    kind TEXT NOT NULL,
+                    username TEXT NOT NULL,
+                    taken_at TEXT NOT NULL,
+                    data TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS snapshots_by_user
+                    ON snapshots (kind, username, taken_at);",
+            )
+            .map_err(|err| err.to_string())?;
+        Ok(Self { connection })
+    }
+
+    // Insert snapshot into the table
+    fn insert(
+        &self,
+        kind: &str,
+        username: &str,
+        taken_at: DateTime<Utc>,
+        data: impl Serialize,
+    ) -> Result<(), String> {
+        let data = serde_json::to_string(&data).map_err(|err| err.to_string())?;
+        self.connection
+            .execute(
+                "INSERT INTO snapshots (kind, username, taken_at, data) VALUES (?1, ?2, ?3, ?4)",
+                params![kind, username, format_time(taken_at), data],
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    // Select snapshots of the kind for the user taken in the time range
+    fn select<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<T>>, String> {
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT taken_at, data FROM snapshots
+                 WHERE kind = ?1 AND username = ?2 AND taken_at >= ?3 AND taken_at < ?4
+                 ORDER BY taken_at, id",
+            )
+            .map_err(|err| err.to_string())?;
+        let rows = statement
+            .query_map(
+                params![kind, username, format_time(from), format_time(to)],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .map_err(|err| err.to_string())?;
+        let mut result = Vec::new();
+        for row in rows {
+            let (taken_at, data) = row.map_err(|err| err.to_string())?;
+            result.push(parse_snapshot(&taken_at, &data)?);
+        }
+        Ok(result)
+    }
+
+    // Select the latest snapshot of the kind for the user
+    fn select_latest<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        username: &str,
+    ) -> Result<Option<Snapshot<T>>, String> {
+        self.connection
+            .query_row(
+                "SELECT taken_at, data FROM snapshots
+                 WHERE kind = ?1 AND username = ?2
+                 ORDER BY taken_at DESC, id DESC
+                 LIMIT 1",
+                params![kind, username],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()
+            .map_err(|err| err.to_string())?
+            .map(|(taken_at, data)| parse_snapshot(&taken_at, &data))
+            .transpose()
+    }
+}
+
+// Parse snapshot from columns of a row
+fn parse_snapshot<T: DeserializeOwned>(taken_at: &str, data: &str) -> Result<Snapshot<T>, String> {
+    Ok(Snapshot {
+        taken_at: DateTime::parse_from_rfc3339(taken_at)
+            .map_err(|err| err.to_string())?
+            .with_timezone(&Utc),
+        data: serde_json::from_str(data).map_err(|err| err.to_string())?,
+    })
+}
+
+// Format time with fixed precision, so formatted times are ordered like times themselves
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+impl SnapshotStore for SqliteStore {
+    fn save_user(&mut self, snapshot: &Snapshot<User>) -> Result<(), String> {
+        self.insert(
+            "user",
+            &snapshot.data.username,
+            snapshot.taken_at,
+            &snapshot.data,
+        )
+    }
+
+    fn save_completed(
+        &mut self,
+        username: &str,
+        snapshot: &Snapshot<CompletedChallenges>,
+    ) -> Result<(), String> {
+        self.insert("completed", username, snapshot.taken_at, &snapshot.data)
+    }
+
+    fn users(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<User>>, String> {
+        self.select("user", username, from, to)
+    }
+
+    fn completed(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<CompletedChallenges>>, String> {
+        self.select("completed", username, from, to)
+    }
+
+    // Times before year 0 or after year 9999 can't be compared as strings, so the range
+    // query of the default implementation doesn't work here
+    fn latest_user(&self, username: &str) -> Result<Option<Snapshot<User>>, String> {
+        self.select_latest("user", username)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for SQLite snapshot store
+
+    use super::*;
+    use crate::fixtures;
+    use chrono::Duration;
+
+    /// Test querying history of overall score
+    #[test]
+    fn test_history() {
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        let now = Utc::now();
+        for days in [3, 2, 1] {
+            let mut data = fixtures::user();
+            data.ranks.overall.score += days;
+            store
+                .save_user(&Snapshot {
+                    taken_at: now - Duration::days(days as i64),
+                    data,
+                })
+                .unwrap();
+        }
+        let history = store
+            .overall_score_history("some_user", now - Duration::days(2), now)
+            .unwrap();
+        assert_eq!(
+            history.iter().map(|point| point.value).collect::<Vec<_>>(),
+            vec![2118, 2117]
+        );
+        assert_eq!(history[0].taken_at, now - Duration::days(2));
+    }
+
+    /// Test getting the latest snapshot of the user
+    #[test]
+    fn test_latest_user() {
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        assert_eq!(store.latest_user("some_user"), Ok(None));
+        let now = Utc::now();
+        for days in [1, 3, 2] {
+            let mut data = fixtures::user();
+            data.honor += days;
+            store
+                .save_user(&Snapshot {
+                    taken_at: now - Duration::days(days as i64),
+                    data,
+                })
+                .unwrap();
+        }
+        let latest = store.latest_user("some_user").unwrap().unwrap();
+        assert_eq!(latest.taken_at, now - Duration::days(1));
+        assert_eq!(latest.data.honor, 545);
+        assert_eq!(store.latest_user("other_user"), Ok(None));
+    }
+}
@@ -0,0 +1,231 @@
+//! Trait for snapshot stores and types of stored entries
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{CompletedChallenges, User};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents a snapshot of data taken at some moment
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Snapshot<T> {
+    /// Date and time when the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// Data of the snapshot
+    pub data: T,
+}
+
+/// Represents a value of some metric at some moment
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Point {
+    /// Date and time when the snapshot with this value was taken
+    pub taken_at: DateTime<Utc>,
+    /// Value of the metric
+    pub value: u64,
+}
+
+/// Storage for snapshots of users and their completed challenges
+///
+/// Snapshots are returned ordered by time when they were taken. Time ranges include start and
+/// exclude end.
+pub trait SnapshotStore {
+    /// Save snapshot of the user
+    fn save_user(&mut self, snapshot: &Snapshot<User>) -> Result<(), String>;
+
+    /// Save snapshot of the completed challenges of the user
+    fn save_completed(
+        &mut self,
+        username: &str,
+        snapshot: &Snapshot<CompletedChallenges>,
+    ) -> Result<(), String>;
+
+    /// Get snapshots of the user taken in the time range
+    fn users(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<User>>, String>;
+
+    /// Get snapshots of completed challenges of the user taken in the time range
+    fn completed(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Snapshot<CompletedChallenges>>, String>;
+
+    /// Get the latest snapshot of the user
+    fn latest_user(&self, username: &str) -> Result<Option<Snapshot<User>>, String> {
+        Ok(self
+            .users(username, DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)?
+            .pop())
+    }
+
+    /// Get honor of the user in every snapshot taken in the time range
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use codewars_api::snapshots::jsonl::JsonLinesStore;
+    /// use codewars_api::snapshots::store::SnapshotStore;
+    ///
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let path = dir.path().join("snapshots.jsonl");
+    /// let store = JsonLinesStore::new(path);
+    /// // Honor over the last 90 days
+    /// let history = store
+    ///     .honor_history("ANKDDEV", Utc::now() - Duration::days(90), Utc::now())
+    ///     .unwrap();
+    /// for point in history {
+    ///     println!("{}: {}", point.taken_at, point.value);
+    /// }
+    /// ```
+    fn honor_history(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Point>, String> {
+        Ok(self
+            .users(username, from, to)?
+            .into_iter()
+            .map(|snapshot| Point {
+                taken_at: snapshot.taken_at,
+                value: snapshot.data.honor,
+            })
+            .collect())
+    }
+
+    /// Get overall score of the user in every snapshot taken in the time range
+    fn overall_score_history(
+        &self,
+        username: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Point>, String> {
+        Ok(self
+            .users(username, from, to)?
+            .into_iter()
+            .map(|snapshot| Point {
+                taken_at: snapshot.taken_at,
+                value: snapshot.data.ranks.overall.score,
+            })
+            .collect())
+    }
+
+    /// Get score of the user in the language in every snapshot taken in the time range
+    ///
+    /// Snapshots taken before user earned rank in the language are skipped
+    fn language_score_history(
+        &self,
+        username: &str,
+        language: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Point>, String> {
+        Ok(self
+            .users(username, from, to)?
+            .into_iter()
+            .filter_map(|snapshot| {
+                snapshot
+                    .data
+                    .ranks
+                    .languages
+                    .get(language)
+                    .map(|rank| Point {
+                        taken_at: snapshot.taken_at,
+                        value: rank.score,
+                    })
+            })
+            .collect())
+    }
+}
+
+/// Fetch current state of the user and save it to the store
+///
+/// Saves user from `get_user` and first page of `get_completed_challenges`. Usernames are
+/// case-insensitive, so both snapshots are saved under the username returned by the API
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * store (&mut impl SnapshotStore) - store to save snapshots to
+/// * username (&str) - username of the user
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::snapshots::jsonl::JsonLinesStore;
+/// use codewars_api::snapshots::store::record;
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut store = JsonLinesStore::new("snapshots.jsonl");
+/// record(&RestCodewarsClient::new(), &mut store, "ANKDDEV").await.unwrap();
+/// # }
+/// ```
+pub async fn record(
+    client: &dyn CodewarsApi,
+    store: &mut impl SnapshotStore,
+    username: &str,
+) -> Result<(), String> {
+    let user = client.get_user(username).await?;
+    let completed = client.get_completed_challenges(username, 0).await?;
+    let taken_at = Utc::now();
+    let username = user.username.clone();
+    store.save_user(&Snapshot {
+        taken_at,
+        data: user,
+    })?;
+    store.save_completed(
+        &username,
+        &Snapshot {
+            taken_at,
+            data: completed,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for recording snapshots
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+    use crate::snapshots::jsonl::JsonLinesStore;
+    use chrono::Duration;
+
+    /// Test that snapshots are saved under the username returned by the API
+    #[tokio::test]
+    async fn test_record_uses_api_username() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api/v1/users/SOME_USER")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock(
+                "GET",
+                "/api/v1/users/SOME_USER/code-challenges/completed?page=0",
+            )
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::COMPLETED)
+            .create_async()
+            .await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JsonLinesStore::new(dir.path().join("snapshots.jsonl"));
+        record(&client, &mut store, "SOME_USER").await.unwrap();
+        let user = store.latest_user("some_user").unwrap().unwrap();
+        assert_eq!(user.data, fixtures::user());
+        let range = (user.taken_at, user.taken_at + Duration::seconds(1));
+        let completed = store.completed("some_user", range.0, range.1).unwrap();
+        assert_eq!(completed.len(), 1);
+        assert!(store
+            .completed("SOME_USER", range.0, range.1)
+            .unwrap()
+            .is_empty());
+    }
+}
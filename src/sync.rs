@@ -0,0 +1,705 @@
+//! Local SQLite mirror of Codewars data of users
+//!
+//! Available with `sync` feature. [`sync_user`] mirrors profile, full completion history,
+//! authored katas and metadata of referenced katas, so they can be analyzed with SQL offline.
+//!
+//! Tables:
+//! * `users` - profiles of users
+//! * `language_ranks` - ranks of users in languages
+//! * `completed` and `completed_languages` - completed katas and languages they were completed in
+//! * `authored` - katas authored by users
+//! * `katas` and `kata_tags` - metadata of katas from `get_kata`
+//! * `deleted_katas` - katas for which `get_kata` returned 404, so they are not fetched again
+//! * `sync_state` - latest completion of users seen by the last finished synchronization
+
+use crate::rest_api::api::get_kata_if_exists;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::{AuthoredChallenge, CodeChallenge, CompletedChallenge, User};
+use chrono::{SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// Schema of the database
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS users (
+    username TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    honor INTEGER NOT NULL,
+    clan TEXT NOT NULL,
+    leaderboard_position INTEGER NOT NULL,
+    overall_rank INTEGER NOT NULL,
+    overall_score INTEGER NOT NULL,
+    total_authored INTEGER NOT NULL,
+    total_completed INTEGER NOT NULL,
+    synced_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS language_ranks (
+    username TEXT NOT NULL,
+    language TEXT NOT NULL,
+    rank INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    PRIMARY KEY (username, language)
+);
+CREATE TABLE IF NOT EXISTS completed (
+    username TEXT NOT NULL,
+    kata_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    completed_at TEXT NOT NULL,
+    PRIMARY KEY (username, kata_id)
+);
+CREATE TABLE IF NOT EXISTS completed_languages (
+    username TEXT NOT NULL,
+    kata_id TEXT NOT NULL,
+    language TEXT NOT NULL,
+    PRIMARY KEY (username, kata_id, language)
+);
+CREATE TABLE IF NOT EXISTS authored (
+    username TEXT NOT NULL,
+    kata_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    rank INTEGER,
+    rank_name TEXT,
+    languages TEXT NOT NULL,
+    PRIMARY KEY (username, kata_id)
+);
+CREATE TABLE IF NOT EXISTS katas (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    url TEXT NOT NULL,
+    category TEXT NOT NULL,
+    rank INTEGER,
+    rank_name TEXT,
+    languages TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    published_at TEXT NOT NULL,
+    approved_at TEXT,
+    total_completed INTEGER NOT NULL,
+    total_attempts INTEGER NOT NULL,
+    total_stars INTEGER NOT NULL,
+    vote_score INTEGER NOT NULL,
+    fetched_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS kata_tags (
+    kata_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (kata_id, tag)
+);
+CREATE TABLE IF NOT EXISTS deleted_katas (
+    id TEXT PRIMARY KEY,
+    checked_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sync_state (
+    username TEXT PRIMARY KEY,
+    completed_at TEXT,
+    synced_at TEXT NOT NULL
+);
+";
+
+/// Options of synchronization
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct SyncOptions {
+    /// Fetch all pages of completed katas, instead of stopping at already known completion
+    pub full: bool,
+    /// Fetch metadata of completed and authored katas which are not in the database yet
+    pub fetch_katas: bool,
+}
+
+/// Default options fetch only new completions and metadata of new katas
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            full: false,
+            fetch_katas: true,
+        }
+    }
+}
+
+/// Represents result of synchronization
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Copy)]
+pub struct SyncReport {
+    /// Number of fetched pages of completed katas
+    pub pages_fetched: u64,
+    /// Number of completions which were not in the database before
+    pub new_completed: u64,
+    /// Number of authored katas
+    pub authored: u64,
+    /// Number of katas fetched with `get_kata`
+    pub katas_fetched: u64,
+}
+
+/// Database with mirrored Codewars data
+#[derive(Debug)]
+pub struct SyncDatabase {
+    connection: Connection,
+}
+
+/// Implementation of SyncDatabase
+impl SyncDatabase {
+    /// Open database in the file, creating the file and tables if they don't exist
+    ///
+    /// # Arguments:
+    /// * path (impl AsRef<Path>) - path to the database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::init(Connection::open(path).map_err(|err| err.to_string())?)
+    }
+
+    /// Open database in memory
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::init(Connection::open_in_memory().map_err(|err| err.to_string())?)
+    }
+
+    // Create tables if they don't exist
+    fn init(connection: Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|err| err.to_string())?;
+        Ok(Self { connection })
+    }
+
+    /// Get connection to the database, e.g. to run SQL queries
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Get time of the latest completion of the user seen by the last synchronization which
+    /// fetched all new pages of completed katas
+    ///
+    /// # Returns:
+    /// * Result<Option<String>, String> - Result that contains `None` if no synchronization of
+    ///   the user has finished yet
+    pub fn completed_watermark(&self, username: &str) -> Result<Option<String>, String> {
+        self.connection
+            .query_row(
+                "SELECT completed_at FROM sync_state WHERE username = ?1",
+                params![username],
+                |row| row.get(0),
+            )
+            .optional()
+            .map(Option::flatten)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Save time of the latest completion of the user seen by a finished synchronization
+    pub fn save_completed_watermark(
+        &mut self,
+        username: &str,
+        completed_at: Option<&str>,
+    ) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO sync_state VALUES (?1, ?2, ?3)",
+                params![username, completed_at, now()],
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Check if metadata of the kata is in the database
+    pub fn has_kata(&self, id: &str) -> Result<bool, String> {
+        self.connection
+            .query_row("SELECT 1 FROM katas WHERE id = ?1", params![id], |_| Ok(()))
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Check if the kata is known to be deleted from Codewars
+    pub fn is_kata_deleted(&self, id: &str) -> Result<bool, String> {
+        self.connection
+            .query_row(
+                "SELECT 1 FROM deleted_katas WHERE id = ?1",
+                params![id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Remember that the kata is deleted from Codewars, so it is not fetched again
+    pub fn save_deleted_kata(&mut self, id: &str) -> Result<(), String> {
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO deleted_katas VALUES (?1, ?2)",
+                params![id, now()],
+            )
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Save profile of the user, replacing the previous one
+    pub fn save_user(&mut self, user: &User) -> Result<(), String> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute(
+                "INSERT OR REPLACE INTO users VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    user.username,
+                    user.name,
+                    user.honor,
+                    user.clan,
+                    user.leaderboard_position,
+                    user.ranks.overall.rank,
+                    user.ranks.overall.score,
+                    user.code_challenges.total_authored,
+                    user.code_challenges.total_completed,
+                    now(),
+                ],
+            )
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute(
+                "DELETE FROM language_ranks WHERE username = ?1",
+                params![user.username],
+            )
+            .map_err(|err| err.to_string())?;
+        for (language, rank) in &user.ranks.languages {
+            transaction
+                .execute(
+                    "INSERT INTO language_ranks VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![user.username, language, rank.rank, rank.name, rank.score],
+                )
+                .map_err(|err| err.to_string())?;
+        }
+        transaction.commit().map_err(|err| err.to_string())
+    }
+
+    /// Save completed katas of the user
+    ///
+    /// # Returns:
+    /// * Result<u64, String> - Result that contains number of completions which were not saved before
+    pub fn save_completed(
+        &mut self,
+        username: &str,
+        completed: &[CompletedChallenge],
+    ) -> Result<u64, String> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| err.to_string())?;
+        let mut new_completed = 0;
+        for kata in completed {
+            let known: bool = transaction
+                .query_row(
+                    "SELECT 1 FROM completed WHERE username = ?1 AND kata_id = ?2 AND completed_at = ?3",
+                    params![username, kata.id, kata.completed_at],
+                    |_| Ok(()),
+                )
+                .optional()
+                .map_err(|err| err.to_string())?
+                .is_some();
+            if !known {
+                new_completed += 1;
+            }
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO completed VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![username, kata.id, kata.name, kata.slug, kata.completed_at],
+                )
+                .map_err(|err| err.to_string())?;
+            for language in &kata.completed_languages {
+                transaction
+                    .execute(
+                        "INSERT OR IGNORE INTO completed_languages VALUES (?1, ?2, ?3)",
+                        params![username, kata.id, language],
+                    )
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        transaction.commit().map_err(|err| err.to_string())?;
+        Ok(new_completed)
+    }
+
+    /// Save authored katas of the user, replacing the previous ones
+    pub fn save_authored(
+        &mut self,
+        username: &str,
+        authored: &[AuthoredChallenge],
+    ) -> Result<(), String> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute(
+                "DELETE FROM authored WHERE username = ?1",
+                params![username],
+            )
+            .map_err(|err| err.to_string())?;
+        for kata in authored {
+            transaction
+                .execute(
+                    "INSERT INTO authored VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        username,
+                        kata.id,
+                        kata.name,
+                        kata.rank,
+                        kata.rank_name,
+                        to_json(&kata.languages)?,
+                    ],
+                )
+                .map_err(|err| err.to_string())?;
+        }
+        transaction.commit().map_err(|err| err.to_string())
+    }
+
+    /// Save metadata of the kata, replacing the previous one
+    pub fn save_kata(&mut self, kata: &CodeChallenge) -> Result<(), String> {
+        let transaction = self
+            .connection
+            .transaction()
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute(
+                "INSERT OR REPLACE INTO katas
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    kata.id,
+                    kata.name,
+                    kata.slug,
+                    kata.url,
+                    kata.category,
+                    kata.rank.as_ref().map(|rank| rank.id),
+                    kata.rank.as_ref().map(|rank| rank.name.clone()),
+                    to_json(&kata.languages)?,
+                    kata.created_by.username,
+                    kata.published_at,
+                    kata.approved_at,
+                    kata.total_completed,
+                    kata.total_attempts,
+                    kata.total_stars,
+                    kata.vote_score,
+                    now(),
+                ],
+            )
+            .map_err(|err| err.to_string())?;
+        transaction
+            .execute("DELETE FROM kata_tags WHERE kata_id = ?1", params![kata.id])
+            .map_err(|err| err.to_string())?;
+        for tag in &kata.tags {
+            transaction
+                .execute(
+                    "INSERT OR IGNORE INTO kata_tags VALUES (?1, ?2)",
+                    params![kata.id, tag],
+                )
+                .map_err(|err| err.to_string())?;
+        }
+        transaction.commit().map_err(|err| err.to_string())
+    }
+}
+
+// Get current time in RFC 3339 format
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// Serialize list to JSON array
+fn to_json(values: &[String]) -> Result<String, String> {
+    serde_json::to_string(values).map_err(|err| err.to_string())
+}
+
+/// Mirror data of the user into the database
+///
+/// Completed katas are fetched page by page. Unless `options.full` is set, paging stops at the
+/// page which contains the latest completion seen by the last finished synchronization. The
+/// watermark is saved only after paging finishes, so a synchronization which was interrupted
+/// is resumed as a full one. Metadata of katas is saved under the ID they are referenced by.
+/// Katas which are deleted from Codewars are remembered and not fetched again.
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * database (&mut SyncDatabase) - database to save data to
+/// * username (&str) - username of the user
+/// * options (SyncOptions) - options of synchronization
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::sync::{sync_user, SyncDatabase, SyncOptions};
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let mut database = SyncDatabase::open("codewars.db").unwrap();
+/// let report = sync_user(&client, &mut database, "ANKDDEV", SyncOptions::default())
+///     .await
+///     .unwrap();
+/// println!("New completions: {}", report.new_completed);
+/// # }
+/// ```
+pub async fn sync_user(
+    client: &dyn CodewarsApi,
+    database: &mut SyncDatabase,
+    username: &str,
+    options: SyncOptions,
+) -> Result<SyncReport, String> {
+    let mut report = SyncReport::default();
+    let user = client.get_user(username).await?;
+    database.save_user(&user)?;
+
+    let watermark = database.completed_watermark(username)?;
+    let latest_known = match options.full {
+        true => None,
+        false => watermark.clone(),
+    };
+    let mut latest_seen = watermark;
+    let mut kata_ids = Vec::new();
+    let mut page = 0;
+    loop {
+        let completed = client.get_completed_challenges(username, page).await?;
+        report.pages_fetched += 1;
+        report.new_completed += database.save_completed(username, &completed.data)?;
+        kata_ids.extend(completed.data.iter().map(|kata| kata.id.clone()));
+        for kata in &completed.data {
+            if latest_seen
+                .as_ref()
+                .is_none_or(|latest| &kata.completed_at > latest)
+            {
+                latest_seen = Some(kata.completed_at.clone());
+            }
+        }
+        // Completions are sorted from the newest, so the rest of pages is already known
+        let reached_known = latest_known.as_ref().is_some_and(|latest| {
+            completed
+                .data
+                .iter()
+                .any(|kata| &kata.completed_at <= latest)
+        });
+        page += 1;
+        if reached_known || u64::from(page) >= completed.total_pages {
+            break;
+        }
+    }
+    database.save_completed_watermark(username, latest_seen.as_deref())?;
+
+    let authored = client.get_authored_challenges(username).await?.data;
+    database.save_authored(username, &authored)?;
+    report.authored = authored.len() as u64;
+    kata_ids.extend(authored.into_iter().map(|kata| kata.id));
+
+    if options.fetch_katas {
+        for id in kata_ids {
+            if database.has_kata(&id)? || database.is_kata_deleted(&id)? {
+                continue;
+            }
+            match get_kata_if_exists(client, &id).await? {
+                Some(kata) => {
+                    // Save under the referenced ID, so the kata is joined with completions and
+                    // not fetched again even if Codewars returns another ID
+                    database.save_kata(&CodeChallenge { id, ..kata })?;
+                    report.katas_fetched += 1;
+                }
+                None => database.save_deleted_kata(&id)?,
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for synchronization
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    // Build page of completed katas with the given IDs and completion times
+    fn page(katas: &[(&str, &str)], total_pages: u64) -> String {
+        let mut page = fixtures::completed();
+        let template = page.data[0].clone();
+        page.total_pages = total_pages;
+        page.data = katas
+            .iter()
+            .map(|(id, completed_at)| CompletedChallenge {
+                id: id.to_string(),
+                completed_at: completed_at.to_string(),
+                ..template.clone()
+            })
+            .collect();
+        serde_json::to_string(&page).unwrap()
+    }
+
+    /// Test full and incremental synchronization
+    #[tokio::test]
+    async fn test_incremental_sync() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut database = SyncDatabase::open_in_memory().unwrap();
+        let completed_path = "/api/v1/users/some_user/code-challenges/completed";
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/some_user/code-challenges/authored")
+            .with_body(fixtures::AUTHORED)
+            .create_async()
+            .await;
+        // Every kata is fetched once, the completed one is also in authored katas
+        let authored = ["5571d9fc11526780a000011a", "51ba717bb08c1cd60f00002f"];
+        let mut kata_mocks = Vec::new();
+        for id in authored.iter().chain(&["c"]) {
+            let mock = server
+                .mock("GET", format!("/api/v1/code-challenges/{}", id).as_str())
+                .with_body(fixtures::KATA.replace("5277c8a221e209d3f6000b56", id))
+                .expect(1)
+                .create_async()
+                .await;
+            kata_mocks.push(mock);
+        }
+        // Codewars returns another ID than the kata is referenced by
+        let mock = server
+            .mock("GET", "/api/v1/code-challenges/b")
+            .with_body(fixtures::KATA)
+            .expect(1)
+            .create_async()
+            .await;
+        kata_mocks.push(mock);
+        let first_page = server
+            .mock("GET", format!("{}?page=0", completed_path).as_str())
+            .with_body(page(&[("b", "2024-01-02T00:00:00Z")], 2))
+            .create_async()
+            .await;
+        let second_page = server
+            .mock("GET", format!("{}?page=1", completed_path).as_str())
+            .with_body(page(&[(authored[0], "2024-01-01T00:00:00Z")], 2))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let report = sync_user(&client, &mut database, "some_user", SyncOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(report.pages_fetched, 2);
+        assert_eq!(report.new_completed, 2);
+        assert_eq!(report.authored, 2);
+        assert_eq!(report.katas_fetched, 3);
+
+        // New completion appears on the first page, second page must not be fetched again
+        first_page.remove_async().await;
+        server
+            .mock("GET", format!("{}?page=0", completed_path).as_str())
+            .with_body(page(
+                &[("c", "2024-01-03T00:00:00Z"), ("b", "2024-01-02T00:00:00Z")],
+                2,
+            ))
+            .create_async()
+            .await;
+        let report = sync_user(&client, &mut database, "some_user", SyncOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(report.pages_fetched, 1);
+        assert_eq!(report.new_completed, 1);
+        assert_eq!(report.katas_fetched, 1);
+        second_page.assert_async().await;
+        for mock in kata_mocks {
+            mock.assert_async().await;
+        }
+
+        let count: u64 = database
+            .connection()
+            .query_row(
+                "SELECT COUNT(*) FROM completed WHERE username = 'some_user'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 3);
+        let ids: Vec<String> = database
+            .connection()
+            .prepare("SELECT id FROM katas ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(ids, vec![authored[1], authored[0], "b", "c"]);
+        let rank: Option<String> = database
+            .connection()
+            .query_row("SELECT rank_name FROM katas WHERE id = 'b'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(rank, Some("4 kyu".to_string()));
+    }
+
+    /// Test resuming interrupted synchronization and skipping deleted katas
+    #[tokio::test]
+    async fn test_resume_interrupted_sync() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut database = SyncDatabase::open_in_memory().unwrap();
+        let completed_path = "/api/v1/users/some_user/code-challenges/completed";
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/users/some_user/code-challenges/authored")
+            .with_body(r#"{"data": []}"#)
+            .create_async()
+            .await;
+        let deleted_kata = server
+            .mock("GET", "/api/v1/code-challenges/deleted")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+        server
+            .mock("GET", format!("{}?page=0", completed_path).as_str())
+            .with_body(page(&[("deleted", "2024-01-02T00:00:00Z")], 2))
+            .create_async()
+            .await;
+        let failing_page = server
+            .mock("GET", format!("{}?page=1", completed_path).as_str())
+            .with_status(503)
+            .create_async()
+            .await;
+        let options = SyncOptions::default();
+        assert!(sync_user(&client, &mut database, "some_user", options)
+            .await
+            .is_err());
+        assert_eq!(database.completed_watermark("some_user"), Ok(None));
+
+        // The first page is known, but paging wasn't finished, so the second page is fetched
+        failing_page.remove_async().await;
+        server
+            .mock("GET", format!("{}?page=1", completed_path).as_str())
+            .with_body(page(&[("a", "2024-01-01T00:00:00Z")], 2))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/code-challenges/a")
+            .with_body(fixtures::KATA)
+            .create_async()
+            .await;
+        let report = sync_user(&client, &mut database, "some_user", options)
+            .await
+            .unwrap();
+        assert_eq!(report.pages_fetched, 2);
+        assert_eq!(report.new_completed, 1);
+        assert_eq!(report.katas_fetched, 1);
+        assert_eq!(
+            database.completed_watermark("some_user"),
+            Ok(Some("2024-01-02T00:00:00Z".to_string()))
+        );
+
+        let report = sync_user(&client, &mut database, "some_user", options)
+            .await
+            .unwrap();
+        assert_eq!(report.pages_fetched, 1);
+        assert_eq!(report.katas_fetched, 0);
+        assert_eq!(database.is_kata_deleted("deleted"), Ok(true));
+        deleted_kata.assert_async().await;
+    }
+}
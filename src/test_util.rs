@@ -0,0 +1,383 @@
+//! Fake Codewars HTTP server for integration tests
+//!
+//! Available with `test-util` feature. [`FakeCodewarsServer`] serves the REST API from a
+//! [`FakeCodewarsApi`] dataset on a local port, so tests exercise the real HTTP client.
+//! [`Fault`]s simulate missing data, rate limiting, server errors and slow responses.
+
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::client::RestCodewarsClient;
+use crate::rest_api::fake::FakeCodewarsApi;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Maximum size of a request head
+const MAX_REQUEST: usize = 8192;
+
+/// Represents a failure simulated by the server
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Fault {
+    /// Respond with `404 Not Found`
+    NotFound,
+    /// Respond with `429 Too Many Requests` and `Retry-After` header in seconds
+    RateLimited {
+        /// Value of `Retry-After` header
+        retry_after: u64,
+    },
+    /// Respond with `500 Internal Server Error`
+    ServerError,
+    /// Wait before responding normally
+    Slow(Duration),
+}
+
+/// Faults waiting to be applied to requests
+#[derive(Debug, Default)]
+struct Faults {
+    // Faults applied to the next requests to any path, one per request
+    next: VecDeque<Fault>,
+    // Faults applied to every request to the path
+    paths: HashMap<String, Fault>,
+    // Paths of received requests with queries
+    requests: Vec<String>,
+}
+
+/// Fake Codewars HTTP server
+///
+/// The server is stopped when it is dropped
+#[derive(Debug)]
+pub struct FakeCodewarsServer {
+    url: String,
+    faults: Arc<Mutex<Faults>>,
+    task: JoinHandle<()>,
+}
+
+/// Implementation of FakeCodewarsServer
+impl FakeCodewarsServer {
+    /// Start server on a random local port, serving data of the fake API
+    ///
+    /// Must be called inside Tokio runtime
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::rest_api::fake::FakeCodewarsApi;
+    /// use codewars_api::test_util::{FakeCodewarsServer, Fault};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let server = FakeCodewarsServer::start(FakeCodewarsApi::with_fixtures())
+    ///     .await
+    ///     .unwrap();
+    /// let client = server.client();
+    /// assert_eq!(client.get_user("some_user").await.unwrap().honor, 544);
+    /// server.fail_next(Fault::RateLimited { retry_after: 30 });
+    /// assert_eq!(
+    ///     client.get_user("some_user").await,
+    ///     Err("unexpected status code: 429 Too Many Requests".to_string())
+    /// );
+    /// # }
+    /// ```
+    pub async fn start(api: FakeCodewarsApi) -> Result<Self, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|err| err.to_string())?;
+        let address = listener.local_addr().map_err(|err| err.to_string())?;
+        let faults = Arc::new(Mutex::new(Faults::default()));
+        let api = Arc::new(api);
+        let shared = Arc::clone(&faults);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let api = Arc::clone(&api);
+                let faults = Arc::clone(&shared);
+                tokio::spawn(async move {
+                    // Client has disconnected, nothing to do
+                    let _ = handle(stream, &api, &faults).await;
+                });
+            }
+        });
+        Ok(Self {
+            url: format!("http://{}", address),
+            faults,
+            task,
+        })
+    }
+
+    /// Get base URL of the server, e.g. `http://127.0.0.1:41234`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Create client that sends requests to the server
+    pub fn client(&self) -> RestCodewarsClient {
+        RestCodewarsClient::with_base_url(&self.url)
+    }
+
+    /// Apply the fault to the next request. Faults are applied in the order they were added
+    pub fn fail_next(&self, fault: Fault) {
+        self.faults.lock().unwrap().next.push_back(fault);
+    }
+
+    /// Apply the fault to every request to the path, e.g. `/api/v1/users/some_user`
+    ///
+    /// Query is ignored when matching the path
+    pub fn fail_path(&self, path: &str, fault: Fault) {
+        let mut faults = self.faults.lock().unwrap();
+        faults.paths.insert(path.to_string(), fault);
+    }
+
+    /// Remove all faults
+    pub fn clear_faults(&self) {
+        let mut faults = self.faults.lock().unwrap();
+        faults.next.clear();
+        faults.paths.clear();
+    }
+
+    /// Get paths with queries of all received requests, in the order they were received
+    pub fn requests(&self) -> Vec<String> {
+        self.faults.lock().unwrap().requests.clone()
+    }
+}
+
+/// Stop server when it is dropped
+impl Drop for FakeCodewarsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// Respond to a single HTTP request
+async fn handle(
+    mut stream: TcpStream,
+    api: &FakeCodewarsApi,
+    faults: &Mutex<Faults>,
+) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    // Read request head, body is ignored
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") && request.len() < MAX_REQUEST {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut parts = request.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let fault = {
+        let mut faults = faults.lock().unwrap();
+        faults.requests.push(target.to_string());
+        faults
+            .next
+            .pop_front()
+            .or_else(|| faults.paths.get(path).copied())
+    };
+    let response = match fault {
+        Some(Fault::NotFound) => error(404, "Not Found", &[]),
+        Some(Fault::RateLimited { retry_after }) => error(
+            429,
+            "Too Many Requests",
+            &[("Retry-After", retry_after.to_string())],
+        ),
+        Some(Fault::ServerError) => error(500, "Internal Server Error", &[]),
+        Some(Fault::Slow(delay)) => {
+            tokio::time::sleep(delay).await;
+            route(api, method, path, query).await
+        }
+        None => route(api, method, path, query).await,
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+// Serve the request from the fake API
+async fn route(api: &FakeCodewarsApi, method: &str, path: &str, query: &str) -> String {
+    if method != "GET" {
+        return error(405, "Method Not Allowed", &[]);
+    }
+    let segments: Vec<String> = path
+        .trim_start_matches("/api/v1/")
+        .split('/')
+        .map(decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        ["users", username] => json(api.get_user(username).await),
+        ["users", username, "code-challenges", "completed"] => {
+            let page = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("page="))
+                .map_or(Ok(0), str::parse);
+            match page {
+                Ok(page) => json(api.get_completed_challenges(username, page).await),
+                Err(_) => error(400, "Bad Request", &[]),
+            }
+        }
+        ["users", username, "code-challenges", "authored"] => {
+            json(api.get_authored_challenges(username).await)
+        }
+        ["code-challenges", slug] => json(api.get_kata(slug).await),
+        _ => error(404, "Not Found", &[]),
+    }
+}
+
+// Build response with data of the fake API, or 404 if it is missing
+fn json<T: Serialize>(result: Result<T, String>) -> String {
+    match result.map(|data| serde_json::to_string(&data)) {
+        Ok(Ok(body)) => response(200, "OK", &[], &body),
+        Ok(Err(_)) => error(500, "Internal Server Error", &[]),
+        Err(_) => error(404, "Not Found", &[]),
+    }
+}
+
+// Build error response with body like Codewars sends
+fn error(status: u16, reason: &str, headers: &[(&str, String)]) -> String {
+    let body = serde_json::json!({"success": false, "reason": reason.to_lowercase()});
+    response(status, reason, headers, &body.to_string())
+}
+
+// Build HTTP response with JSON body
+fn response(status: u16, reason: &str, headers: &[(&str, String)], body: &str) -> String {
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        reason,
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    response
+}
+
+// Decode percent-encoded path segment
+fn decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for fake Codewars server
+
+    use super::*;
+    use crate::rest_api::models::{CompletedChallenge, User};
+    use std::time::Instant;
+
+    /// Test serving data through the real client
+    #[tokio::test]
+    async fn test_serve_data() {
+        let user: User = FakeCodewarsApi::with_fixtures()
+            .get_user("some_user")
+            .await
+            .unwrap();
+        let completed: Vec<CompletedChallenge> = (0..250)
+            .map(|index| CompletedChallenge {
+                id: index.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let api = FakeCodewarsApi::with_fixtures()
+            .with_user(User {
+                username: "some user".to_string(),
+                ..user
+            })
+            .with_completed("some user", completed.clone());
+        let server = FakeCodewarsServer::start(api).await.unwrap();
+        let client = server.client();
+        assert_eq!(client.get_user("some_user").await.unwrap().honor, 544);
+        assert_eq!(
+            client.get_kata("valid-braces").await.unwrap().name,
+            "Valid Braces"
+        );
+        assert_eq!(
+            client
+                .get_authored_challenges("some_user")
+                .await
+                .unwrap()
+                .data
+                .len(),
+            2
+        );
+        assert_eq!(
+            client.get_all_completed_challenges("some user").await,
+            Ok(completed)
+        );
+        assert_eq!(
+            client.get_user("nobody").await,
+            Err("unexpected status code: 404 Not Found".to_string())
+        );
+        assert_eq!(
+            server.requests(),
+            vec![
+                "/api/v1/users/some_user",
+                "/api/v1/code-challenges/valid-braces",
+                "/api/v1/users/some_user/code-challenges/authored",
+                "/api/v1/users/some%20user/code-challenges/completed?page=0",
+                "/api/v1/users/some%20user/code-challenges/completed?page=1",
+                "/api/v1/users/nobody",
+            ]
+        );
+    }
+
+    /// Test simulating failures
+    #[tokio::test]
+    async fn test_faults() {
+        let server = FakeCodewarsServer::start(FakeCodewarsApi::with_fixtures())
+            .await
+            .unwrap();
+        let client = server.client();
+        server.fail_next(Fault::ServerError);
+        server.fail_next(Fault::NotFound);
+        assert_eq!(
+            client.get_user("some_user").await,
+            Err("unexpected status code: 500 Internal Server Error".to_string())
+        );
+        assert_eq!(
+            client.get_user("some_user").await,
+            Err("unexpected status code: 404 Not Found".to_string())
+        );
+        assert!(client.get_user("some_user").await.is_ok());
+
+        server.fail_path(
+            "/api/v1/users/some_user",
+            Fault::RateLimited { retry_after: 30 },
+        );
+        let response = reqwest::get(format!("{}/api/v1/users/some_user", server.url()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "30");
+        assert!(client.get_kata("valid-braces").await.is_ok());
+
+        server.clear_faults();
+        server.fail_next(Fault::Slow(Duration::from_millis(200)));
+        let started = Instant::now();
+        assert!(client.get_user("some_user").await.is_ok());
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,175 @@
+//! Missing translations of authored katas
+//!
+//! Gaps are sorted by popularity of katas, so translation work can start from the most
+//! completed katas.
+
+use crate::rest_api::api::get_kata_if_exists;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::models::AuthoredChallenge;
+use serde::{Deserialize, Serialize};
+
+/// Represents an authored kata which lacks translations
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TranslationGap {
+    /// ID of the kata
+    pub id: String,
+    /// Name of the kata
+    pub name: String,
+    /// Rank name of the kata, if it is approved
+    pub rank: Option<String>,
+    /// Languages the kata is available in
+    pub languages: Vec<String>,
+    /// Target languages the kata isn't available in
+    pub missing: Vec<String>,
+    /// Number of users who completed the kata, 0 if popularity wasn't fetched
+    pub total_completed: u64,
+    /// Vote score of the kata, 0 if popularity wasn't fetched
+    pub vote_score: u64,
+}
+
+/// Implementation of TranslationGap
+impl TranslationGap {
+    /// Find target languages the kata isn't available in
+    ///
+    /// Returns `None` if the kata is available in all target languages
+    ///
+    /// # Arguments:
+    /// * kata (&AuthoredChallenge) - authored kata
+    /// * targets (&[&str]) - languages the kata should be available in
+    pub fn new(kata: &AuthoredChallenge, targets: &[&str]) -> Option<Self> {
+        let missing: Vec<String> = targets
+            .iter()
+            .filter(|language| {
+                !kata
+                    .languages
+                    .iter()
+                    .any(|available| available == *language)
+            })
+            .map(|language| language.to_string())
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+        Some(Self {
+            id: kata.id.clone(),
+            name: kata.name.clone(),
+            rank: kata.rank_name.clone(),
+            languages: kata.languages.clone(),
+            missing,
+            total_completed: 0,
+            vote_score: 0,
+        })
+    }
+}
+
+/// Sort gaps from the most popular kata, by number of completions and then by vote score
+pub fn sort_by_popularity(gaps: &mut [TranslationGap]) {
+    gaps.sort_by(|a, b| {
+        b.total_completed
+            .cmp(&a.total_completed)
+            .then(b.vote_score.cmp(&a.vote_score))
+            .then(a.name.cmp(&b.name))
+    });
+}
+
+/// Find missing translations of katas authored by the user
+///
+/// Popularity of every kata with missing translations is fetched with `get_kata`.
+/// Katas which can't be found keep zero popularity
+///
+/// # Arguments:
+/// * client (&dyn CodewarsApi) - client to fetch data with
+/// * username (&str) - username of the author
+/// * targets (&[&str]) - languages katas should be available in
+///
+/// # Examples
+/// ```no_run
+/// use codewars_api::translations::find_translation_gaps;
+/// use codewars_api::RestCodewarsClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = RestCodewarsClient::new();
+/// let gaps = find_translation_gaps(&client, "ANKDDEV", &["rust", "python"]).await.unwrap();
+/// for gap in gaps {
+///     println!("{}: {}", gap.name, gap.missing.join(", "));
+/// }
+/// # }
+/// ```
+pub async fn find_translation_gaps(
+    client: &dyn CodewarsApi,
+    username: &str,
+    targets: &[&str],
+) -> Result<Vec<TranslationGap>, String> {
+    let authored = client.get_authored_challenges(username).await?;
+    let mut gaps: Vec<TranslationGap> = authored
+        .data
+        .iter()
+        .filter_map(|kata| TranslationGap::new(kata, targets))
+        .collect();
+    for gap in &mut gaps {
+        // Kata can be deleted or not published yet
+        if let Some(kata) = get_kata_if_exists(client, &gap.id).await? {
+            gap.total_completed = kata.total_completed;
+            gap.vote_score = kata.vote_score;
+        }
+    }
+    sort_by_popularity(&mut gaps);
+    Ok(gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for translation gaps
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::client::RestCodewarsClient;
+
+    /// Test finding gaps sorted by popularity
+    #[tokio::test]
+    async fn test_find_translation_gaps() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        server
+            .mock("GET", "/api/v1/users/some_user/code-challenges/authored")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::AUTHORED)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/code-challenges/5571d9fc11526780a000011a")
+            .with_status(404)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/api/v1/code-challenges/51ba717bb08c1cd60f00002f")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::KATA)
+            .create_async()
+            .await;
+        let gaps = find_translation_gaps(&client, "some_user", &["rust", "java", "ruby"])
+            .await
+            .unwrap();
+        assert_eq!(
+            gaps.iter()
+                .map(|gap| (gap.name.as_str(), gap.missing.clone(), gap.total_completed))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Range Extraction", vec!["rust".to_string()], 919),
+                (
+                    "The builder of things",
+                    vec!["rust".to_string(), "java".to_string()],
+                    0
+                ),
+            ]
+        );
+        assert_eq!(gaps[0].vote_score, 512);
+        assert_eq!(
+            find_translation_gaps(&client, "some_user", &["ruby"])
+                .await
+                .unwrap(),
+            Vec::new()
+        );
+    }
+}
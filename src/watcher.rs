@@ -0,0 +1,353 @@
+//! Polling-based watcher for changes of users
+//!
+//! Codewars webhooks are sent only for the account that configured them. [`UserWatcher`] polls
+//! the REST API for any set of users instead, and emits events when something changes.
+
+use crate::diff::UserDiff;
+use crate::rest_api::api::CodewarsApi;
+use crate::rest_api::client::RestCodewarsClient;
+use crate::rest_api::models::{CompletedChallenge, CompletedChallenges, Rank, User};
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Represents a change of a watched user
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct WatchEvent {
+    /// Username of the user
+    pub username: String,
+    /// What has changed
+    pub change: UserChange,
+}
+
+/// Represents what has changed for a user between two polls
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UserChange {
+    /// User's honor was changed
+    HonorChanged {
+        /// New honor of the user
+        honor: u64,
+        /// Honor of the user before the change
+        honor_was: u64,
+    },
+    /// User's rank was changed or earned in a new language
+    RankChanged {
+        /// Language of the rank, or `None` for overall rank
+        language: Option<String>,
+        /// New rank
+        rank: Rank,
+        /// Rank before the change, or `None` if rank was just earned
+        rank_was: Option<Rank>,
+    },
+    /// User completed a kata
+    KataCompleted {
+        /// Completed kata
+        kata: CompletedChallenge,
+    },
+    /// User authored new katas
+    KataAuthored {
+        /// New number of authored katas
+        total_authored: u64,
+        /// Number of authored katas before the change
+        total_authored_was: u64,
+    },
+}
+
+/// State of a user at the moment of poll
+#[derive(Debug, Clone)]
+struct Snapshot {
+    user: User,
+    // IDs of all completed katas seen so far
+    completed_ids: HashSet<String>,
+}
+
+/// Watcher that polls users on an interval and emits their changes
+///
+/// Every poll fetches user with `get_user` and first page of `get_completed_challenges`.
+/// First poll of each user only remembers its state, events are emitted starting from the second one.
+#[derive(Debug, Clone)]
+pub struct UserWatcher<A = RestCodewarsClient> {
+    client: A,
+    usernames: Vec<String>,
+    interval: Duration,
+    snapshots: HashMap<String, Snapshot>,
+}
+
+/// Implementation of UserWatcher
+impl<A: CodewarsApi> UserWatcher<A> {
+    /// Create new watcher
+    ///
+    /// # Arguments:
+    /// * client (A) - client used for polling, any implementation of [`CodewarsApi`]
+    /// * usernames (&[&str]) - usernames of watched users
+    /// * interval (Duration) - time between polls
+    pub fn new(client: A, usernames: &[&str], interval: Duration) -> Self {
+        Self {
+            client,
+            usernames: usernames.iter().map(|name| name.to_string()).collect(),
+            interval,
+            snapshots: HashMap::new(),
+        }
+    }
+
+    /// Poll all users once and return their changes since the previous poll
+    ///
+    /// Errors for one user don't stop polling of others, they are returned along with events
+    pub async fn poll(&mut self) -> Vec<Result<WatchEvent, String>> {
+        let mut result = Vec::new();
+        for username in self.usernames.clone() {
+            match self.fetch(&username).await {
+                Ok((user, completed)) => {
+                    result.extend(self.update(&username, user, completed).into_iter().map(
+                        |change| {
+                            Ok(WatchEvent {
+                                username: username.clone(),
+                                change,
+                            })
+                        },
+                    ))
+                }
+                Err(err) => result.push(Err(format!("{}: {}", username, err))),
+            }
+        }
+        result
+    }
+
+    /// Turn watcher into a stream of changes, polling users on the interval
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::watcher::UserWatcher;
+    /// use codewars_api::RestCodewarsClient;
+    /// use futures_util::StreamExt;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let watcher = UserWatcher::new(
+    ///     RestCodewarsClient::new(),
+    ///     &["ANKDDEV", "Dentzil"],
+    ///     Duration::from_secs(300),
+    /// );
+    /// let mut events = Box::pin(watcher.watch());
+    /// while let Some(event) = events.next().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    pub fn watch(self) -> impl Stream<Item = Result<WatchEvent, String>> {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        stream::unfold(
+            (self, interval, VecDeque::new()),
+            |(mut watcher, mut interval, mut queue)| async move {
+                loop {
+                    if let Some(item) = queue.pop_front() {
+                        return Some((item, (watcher, interval, queue)));
+                    }
+                    interval.tick().await;
+                    queue.extend(watcher.poll().await);
+                }
+            },
+        )
+    }
+
+    // Fetch current state of the user
+    async fn fetch(&self, username: &str) -> Result<(User, CompletedChallenges), String> {
+        let user = self.client.get_user(username).await?;
+        let completed = self.client.get_completed_challenges(username, 0).await?;
+        Ok((user, completed))
+    }
+
+    // Remember new state of the user and return changes since the previous one
+    fn update(
+        &mut self,
+        username: &str,
+        user: User,
+        completed: CompletedChallenges,
+    ) -> Vec<UserChange> {
+        match self.snapshots.get_mut(username) {
+            Some(snapshot) => {
+                let mut changes = diff_users(&snapshot.user, &user);
+                // Completed list is sorted from the newest, so report new katas from the oldest
+                for kata in completed.data.into_iter().rev() {
+                    if snapshot.completed_ids.insert(kata.id.clone()) {
+                        changes.push(UserChange::KataCompleted { kata });
+                    }
+                }
+                snapshot.user = user;
+                changes
+            }
+            None => {
+                let completed_ids = completed.data.into_iter().map(|kata| kata.id).collect();
+                self.snapshots.insert(
+                    username.to_string(),
+                    Snapshot {
+                        user,
+                        completed_ids,
+                    },
+                );
+                Vec::new()
+            }
+        }
+    }
+}
+
+// Find changes of honor, ranks and authored katas between two states of the user
+fn diff_users(old: &User, new: &User) -> Vec<UserChange> {
+    let diff = UserDiff::new(old, new);
+    let mut changes = Vec::new();
+    if diff.honor.is_changed() {
+        changes.push(UserChange::HonorChanged {
+            honor: diff.honor.after,
+            honor_was: diff.honor.before,
+        });
+    }
+    // Changes of score only are too noisy, so only changes of rank are reported
+    let rank_changes = diff
+        .overall_rank
+        .into_iter()
+        .map(|change| (None, change))
+        .chain(
+            diff.languages
+                .into_iter()
+                .map(|(language, change)| (Some(language), change)),
+        )
+        .filter(|(_, change)| change.is_rank_changed());
+    for (language, change) in rank_changes {
+        changes.push(UserChange::RankChanged {
+            language,
+            rank: change.after,
+            rank_was: Some(change.before),
+        });
+    }
+    for (language, rank) in diff.new_languages {
+        changes.push(UserChange::RankChanged {
+            language: Some(language),
+            rank,
+            rank_was: None,
+        });
+    }
+    if diff.total_authored.delta > 0 {
+        changes.push(UserChange::KataAuthored {
+            total_authored: diff.total_authored.after,
+            total_authored_was: diff.total_authored.before,
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for user watcher
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::fake::FakeCodewarsApi;
+
+    /// Test that changes between polls are emitted as events
+    #[tokio::test]
+    async fn test_poll_emits_changes() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut watcher = UserWatcher::new(client, &["some_user"], Duration::from_secs(1));
+        let user_mock = server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::USER)
+            .create_async()
+            .await;
+        let completed_mock = server
+            .mock(
+                "GET",
+                "/api/v1/users/some_user/code-challenges/completed?page=0",
+            )
+            .with_header("content-type", "application/json")
+            .with_body(fixtures::COMPLETED)
+            .create_async()
+            .await;
+        // First poll only remembers state
+        assert!(watcher.poll().await.is_empty());
+
+        let mut user = fixtures::user();
+        user.honor += 10;
+        user.ranks.languages.get_mut("ruby").unwrap().rank = -3;
+        let mut completed = fixtures::completed();
+        let mut kata = completed.data[0].clone();
+        kata.id = "new_kata".to_string();
+        completed.data.insert(0, kata.clone());
+        user_mock.remove_async().await;
+        completed_mock.remove_async().await;
+        server
+            .mock("GET", "/api/v1/users/some_user")
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&user).unwrap())
+            .create_async()
+            .await;
+        server
+            .mock(
+                "GET",
+                "/api/v1/users/some_user/code-challenges/completed?page=0",
+            )
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&completed).unwrap())
+            .create_async()
+            .await;
+
+        let changes: Vec<UserChange> = watcher
+            .poll()
+            .await
+            .into_iter()
+            .map(|event| event.unwrap().change)
+            .collect();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes[0],
+            UserChange::HonorChanged {
+                honor: 554,
+                honor_was: 544
+            }
+        );
+        assert!(matches!(
+            &changes[1],
+            UserChange::RankChanged { language: Some(language), rank, .. }
+                if language == "ruby" && rank.rank == -3
+        ));
+        assert_eq!(changes[2], UserChange::KataCompleted { kata });
+    }
+
+    /// Test that errors are reported with username
+    #[tokio::test]
+    async fn test_poll_reports_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let client = RestCodewarsClient::new_with_custom_host(server.url());
+        let mut watcher = UserWatcher::new(client, &["missing"], Duration::from_secs(1));
+        server
+            .mock("GET", "/api/v1/users/missing")
+            .with_status(404)
+            .create_async()
+            .await;
+        assert_eq!(
+            watcher.poll().await,
+            vec![Err(
+                "missing: unexpected status code: 404 Not Found".to_string()
+            )]
+        );
+    }
+
+    /// Test that watcher works with any implementation of the API
+    #[tokio::test]
+    async fn test_poll_fake_api() {
+        let api = FakeCodewarsApi::with_fixtures();
+        let mut watcher = UserWatcher::new(api, &["some_user", "missing"], Duration::from_secs(1));
+        let errors = vec![Err(
+            "missing: unexpected status code: 404 Not Found".to_string()
+        )];
+        // First poll only remembers state, and nothing changes in the fake afterwards
+        assert_eq!(watcher.poll().await, errors);
+        assert_eq!(watcher.poll().await, errors);
+    }
+}
@@ -0,0 +1,8 @@
+//! A module for receiving Codewars webhooks
+//!
+//! Read more at [Codewars documentation](https://dev.codewars.com/#webhooks)
+
+pub mod inbox;
+pub mod models;
+pub mod receiver;
+pub mod simulator;
@@ -0,0 +1,399 @@
+//! Durable storage for raw webhook deliveries
+//!
+//! Codewars can retry deliveries or send them out of order, so receiver stores every delivery
+//! before running handlers and marks it as processed only after handlers succeeded.
+
+use crate::webhooks::models::WebhookEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Represents a raw webhook delivery
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Delivery {
+    /// ID used to deduplicate deliveries. Either event ID or hash of the payload
+    pub id: String,
+    /// Value of `X-Webhook-Event` header
+    pub event: String,
+    /// Raw body of the request
+    pub body: String,
+    /// Date and time when the delivery was received
+    pub received_at: DateTime<Utc>,
+}
+
+/// Implementation of Delivery
+impl Delivery {
+    /// Create new delivery received now, identified by hash of the payload
+    ///
+    /// # Arguments:
+    /// * event (&str) - value of `X-Webhook-Event` header
+    /// * body (&str) - raw body of the request
+    pub fn new(event: &str, body: &str) -> Self {
+        Self {
+            id: Self::payload_hash(event, body),
+            event: event.to_string(),
+            body: body.to_string(),
+            received_at: Utc::now(),
+        }
+    }
+
+    /// Create new delivery received now, identified by event ID
+    ///
+    /// # Arguments:
+    /// * id (&str) - ID of the event
+    /// * event (&str) - value of `X-Webhook-Event` header
+    /// * body (&str) - raw body of the request
+    pub fn with_id(id: &str, event: &str, body: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ..Self::new(event, body)
+        }
+    }
+
+    /// Get SHA-256 hash of the event name and body in hex
+    pub fn payload_hash(event: &str, body: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(event.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(body.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Parse event from this delivery
+    ///
+    /// # Errors:
+    /// See [`WebhookEvent::parse`]
+    pub fn parse(&self) -> Result<WebhookEvent, String> {
+        WebhookEvent::parse(&self.event, &self.body)
+    }
+}
+
+/// Storage for webhook deliveries
+///
+/// Implement this trait to store deliveries somewhere else, e.g. in a database
+pub trait WebhookInbox {
+    /// Store delivery
+    ///
+    /// # Returns:
+    /// * Result<bool, String> - `false` if delivery with the same ID was already stored
+    fn store(&mut self, delivery: Delivery) -> Result<bool, String>;
+
+    /// Mark delivery as processed, so it won't be returned by [`WebhookInbox::pending`] anymore
+    fn mark_processed(&mut self, id: &str) -> Result<(), String>;
+
+    /// Get deliveries which were not processed yet, ordered by time of receiving
+    fn pending(&self) -> Result<Vec<Delivery>, String>;
+
+    /// Get all deliveries received in the time range, ordered by time of receiving
+    ///
+    /// # Arguments:
+    /// * from (DateTime<Utc>) - start of the range, inclusive
+    /// * to (DateTime<Utc>) - end of the range, exclusive
+    fn received_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Delivery>, String>;
+}
+
+/// Inbox that stores deliveries in memory
+///
+/// Deliveries are lost on restart, so this is mostly useful for tests
+#[derive(Debug, Default, Clone)]
+pub struct MemoryInbox {
+    deliveries: Vec<Delivery>,
+    ids: HashSet<String>,
+    processed: HashSet<String>,
+}
+
+/// Implementation of MemoryInbox
+impl MemoryInbox {
+    /// Create new empty inbox
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Return deliveries matching the predicate, ordered by time of receiving
+    fn filtered(&self, predicate: impl Fn(&Delivery) -> bool) -> Vec<Delivery> {
+        let mut result: Vec<Delivery> = self
+            .deliveries
+            .iter()
+            .filter(|delivery| predicate(delivery))
+            .cloned()
+            .collect();
+        // Sort is stable, so deliveries received at the same time keep their order
+        result.sort_by_key(|delivery| delivery.received_at);
+        result
+    }
+}
+
+impl WebhookInbox for MemoryInbox {
+    fn store(&mut self, delivery: Delivery) -> Result<bool, String> {
+        if !self.ids.insert(delivery.id.clone()) {
+            return Ok(false);
+        }
+        self.deliveries.push(delivery);
+        Ok(true)
+    }
+
+    fn mark_processed(&mut self, id: &str) -> Result<(), String> {
+        if !self.ids.contains(id) {
+            return Err(format!("unknown delivery: {}", id));
+        }
+        self.processed.insert(id.to_string());
+        Ok(())
+    }
+
+    fn pending(&self) -> Result<Vec<Delivery>, String> {
+        Ok(self.filtered(|delivery| !self.processed.contains(&delivery.id)))
+    }
+
+    fn received_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Delivery>, String> {
+        Ok(self.filtered(|delivery| delivery.received_at >= from && delivery.received_at < to))
+    }
+}
+
+/// Record in the file of [`FileInbox`]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Delivery(Delivery),
+    Processed { id: String },
+}
+
+/// Inbox that stores deliveries in a JSON Lines file
+///
+/// Every delivery and every processed mark is appended to the file and flushed to disk
+/// before method returns, so pending deliveries survive restarts.
+#[derive(Debug)]
+pub struct FileInbox {
+    path: PathBuf,
+    file: File,
+    memory: MemoryInbox,
+}
+
+/// Implementation of FileInbox
+impl FileInbox {
+    /// Open inbox stored in the file, creating the file if it doesn't exist
+    ///
+    /// # Arguments:
+    /// * path (impl AsRef<Path>) - path to the file
+    ///
+    /// # Errors:
+    /// * I/O error message - If the file can't be opened, read or truncated
+    /// * `malformed record on line {line}` - If the file contains a record that can't be parsed
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use codewars_api::webhooks::inbox::{Delivery, FileInbox, WebhookInbox};
+    ///
+    /// let mut inbox = FileInbox::open("webhooks.jsonl").unwrap();
+    /// inbox.store(Delivery::new("user", "{}")).unwrap();
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| err.to_string())?;
+        let mut content = String::new();
+        (&file)
+            .read_to_string(&mut content)
+            .map_err(|err| err.to_string())?;
+        let mut memory = MemoryInbox::new();
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        // Length of the file up to the end of the last valid record
+        let mut valid_len = 0;
+        for (index, line) in lines.iter().enumerate() {
+            if !line.trim().is_empty() {
+                match serde_json::from_str::<Record>(line.trim()) {
+                    Ok(Record::Delivery(delivery)) => {
+                        memory.store(delivery)?;
+                    }
+                    Ok(Record::Processed { id }) => memory.mark_processed(&id)?,
+                    // Last line can be incomplete if process was killed while writing it,
+                    // complete records end with a newline
+                    Err(_) if !line.ends_with('\n') => break,
+                    Err(_) => return Err(format!("malformed record on line {}", index + 1)),
+                }
+            }
+            valid_len += line.len();
+        }
+        // Cut incomplete record, so new records are appended after the last valid one
+        file.set_len(valid_len as u64)
+            .map_err(|err| err.to_string())?;
+        let mut inbox = Self { path, file, memory };
+        if !content[..valid_len].is_empty() && !content[..valid_len].ends_with('\n') {
+            inbox.file.write_all(b"\n").map_err(|err| err.to_string())?;
+        }
+        Ok(inbox)
+    }
+
+    /// Get path to the file of this inbox
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Append record to the file and wait until it is written to disk
+    fn append(&mut self, record: &Record) -> Result<(), String> {
+        let mut line = serde_json::to_string(record).map_err(|err| err.to_string())?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .and_then(|_| self.file.sync_data())
+            .map_err(|err| err.to_string())
+    }
+}
+
+impl WebhookInbox for FileInbox {
+    fn store(&mut self, delivery: Delivery) -> Result<bool, String> {
+        if self.memory.ids.contains(&delivery.id) {
+            return Ok(false);
+        }
+        self.append(&Record::Delivery(delivery.clone()))?;
+        self.memory.store(delivery)
+    }
+
+    fn mark_processed(&mut self, id: &str) -> Result<(), String> {
+        if !self.memory.ids.contains(id) {
+            return Err(format!("unknown delivery: {}", id));
+        }
+        self.append(&Record::Processed { id: id.to_string() })?;
+        self.memory.mark_processed(id)
+    }
+
+    fn pending(&self) -> Result<Vec<Delivery>, String> {
+        self.memory.pending()
+    }
+
+    fn received_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Delivery>, String> {
+        self.memory.received_between(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for webhook inboxes
+
+    use super::*;
+    use chrono::Duration;
+
+    /// Test that deliveries with the same payload are stored once
+    #[test]
+    fn test_deduplicate_by_payload_hash() {
+        let mut inbox = MemoryInbox::new();
+        assert!(inbox.store(Delivery::new("user", "{}")).unwrap());
+        assert!(!inbox.store(Delivery::new("user", "{}")).unwrap());
+        assert!(inbox.store(Delivery::new("code_challenge", "{}")).unwrap());
+        assert_eq!(inbox.pending().unwrap().len(), 2);
+    }
+
+    /// Test that deliveries with the same event ID are stored once
+    #[test]
+    fn test_deduplicate_by_event_id() {
+        let mut inbox = MemoryInbox::new();
+        assert!(inbox.store(Delivery::with_id("1", "user", "{}")).unwrap());
+        assert!(!inbox
+            .store(Delivery::with_id("1", "user", "{\"a\":1}"))
+            .unwrap());
+    }
+
+    /// Test that pending deliveries are ordered by time of receiving
+    #[test]
+    fn test_pending_ordered_by_received_at() {
+        let mut inbox = MemoryInbox::new();
+        let mut late = Delivery::new("user", "late");
+        late.received_at += Duration::seconds(10);
+        let early = Delivery::new("user", "early");
+        inbox.store(late).unwrap();
+        inbox.store(early).unwrap();
+        let bodies: Vec<String> = inbox
+            .pending()
+            .unwrap()
+            .into_iter()
+            .map(|d| d.body)
+            .collect();
+        assert_eq!(bodies, vec!["early", "late"]);
+    }
+
+    /// Test that file inbox keeps deliveries and processed marks after reopening
+    #[test]
+    fn test_file_inbox_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inbox.jsonl");
+        {
+            let mut inbox = FileInbox::open(&path).unwrap();
+            inbox
+                .store(Delivery::with_id("1", "user", "first"))
+                .unwrap();
+            inbox
+                .store(Delivery::with_id("2", "user", "second"))
+                .unwrap();
+            inbox.mark_processed("1").unwrap();
+        }
+        let mut inbox = FileInbox::open(&path).unwrap();
+        let pending = inbox.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "2");
+        assert!(!inbox
+            .store(Delivery::with_id("1", "user", "first"))
+            .unwrap());
+    }
+
+    /// Test that incomplete last line is ignored
+    #[test]
+    fn test_file_inbox_ignores_truncated_last_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inbox.jsonl");
+        {
+            let mut inbox = FileInbox::open(&path).unwrap();
+            inbox
+                .store(Delivery::with_id("1", "user", "first"))
+                .unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"deliv").unwrap();
+        let mut inbox = FileInbox::open(&path).unwrap();
+        assert_eq!(inbox.pending().unwrap().len(), 1);
+        inbox
+            .store(Delivery::with_id("2", "user", "second"))
+            .unwrap();
+        let inbox = FileInbox::open(&path).unwrap();
+        assert_eq!(inbox.pending().unwrap().len(), 2);
+    }
+
+    /// Test that complete last line which can't be parsed is an error and isn't cut
+    #[test]
+    fn test_file_inbox_rejects_malformed_last_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inbox.jsonl");
+        {
+            let mut inbox = FileInbox::open(&path).unwrap();
+            inbox
+                .store(Delivery::with_id("1", "user", "first"))
+                .unwrap();
+        }
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"type\":\"unknown\"}\n").unwrap();
+        let length = std::fs::metadata(&path).unwrap().len();
+        assert_eq!(
+            FileInbox::open(&path).err(),
+            Some("malformed record on line 2".to_string())
+        );
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), length);
+    }
+}
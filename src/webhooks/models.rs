@@ -0,0 +1,150 @@
+//! Models that represent the events sent by Codewars webhooks
+
+use serde::{Deserialize, Serialize};
+
+/// Represents an event sent by Codewars webhook
+///
+/// Kind of event is sent in `X-Webhook-Event` header and action is sent in the body.
+/// Read more at [Codewars documentation](https://dev.codewars.com/#webhooks)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "event", content = "payload", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// Event about code challenge, sent with `X-Webhook-Event: code_challenge`
+    CodeChallenge(CodeChallengeEvent),
+    /// Event about user, sent with `X-Webhook-Event: user`
+    User(UserEvent),
+}
+
+/// Implementation of WebhookEvent
+impl WebhookEvent {
+    /// Parse event from value of `X-Webhook-Event` header and body of the request
+    ///
+    /// # Arguments:
+    /// * event (&str) - value of `X-Webhook-Event` header
+    /// * body (&str) - body of the request
+    ///
+    /// # Returns:
+    /// * Result<WebhookEvent, String> - Result that contains the event or an error message
+    ///
+    /// # Errors:
+    /// * `unknown webhook event: {event}` - If the event is neither `user` nor `code_challenge`
+    /// * `error decoding body` - If there is an error decoding the body with serde
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::webhooks::models::{UserEvent, WebhookEvent};
+    ///
+    /// let body = r#"{"action":"honor_changed","user":{"id":"5a1b","honor":58,"honor_was":56}}"#;
+    /// let event = WebhookEvent::parse("user", body).unwrap();
+    /// assert!(matches!(event, WebhookEvent::User(UserEvent::HonorChanged { .. })));
+    /// ```
+    pub fn parse(event: &str, body: &str) -> Result<WebhookEvent, String> {
+        match event {
+            "user" => serde_json::from_str(body)
+                .map(WebhookEvent::User)
+                .map_err(|err| err.to_string()),
+            "code_challenge" => serde_json::from_str(body)
+                .map(WebhookEvent::CodeChallenge)
+                .map_err(|err| err.to_string()),
+            other => Err(format!("unknown webhook event: {}", other)),
+        }
+    }
+
+    /// Get value of `X-Webhook-Event` header for this event
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            WebhookEvent::CodeChallenge(_) => "code_challenge",
+            WebhookEvent::User(_) => "user",
+        }
+    }
+
+    /// Get body of the request for this event, as Codewars sends it
+    pub fn body(&self) -> String {
+        // Serializing these types can't fail, because they contain only strings and numbers
+        match self {
+            WebhookEvent::CodeChallenge(event) => serde_json::to_string(event).unwrap(),
+            WebhookEvent::User(event) => serde_json::to_string(event).unwrap(),
+        }
+    }
+}
+
+/// Represents an event about code challenge
+///
+/// Read more at [Codewars documentation](https://dev.codewars.com/#webhooks)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum CodeChallengeEvent {
+    /// Code challenge was created
+    Created {
+        /// Code challenge that was created
+        code_challenge: CodeChallengeInfo,
+    },
+    /// Code challenge was approved
+    Approved {
+        /// Code challenge that was approved
+        code_challenge: CodeChallengeInfo,
+    },
+    /// Someone voted for the code challenge
+    Voted {
+        /// Code challenge that was voted for
+        code_challenge: CodeChallengeInfo,
+    },
+}
+
+/// Represents a code challenge in webhook event
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct CodeChallengeInfo {
+    /// ID of the kata
+    pub id: String,
+    /// ID of the author of the kata
+    pub created_by_id: String,
+    /// ID of the approver of the kata, if it was approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by_id: Option<String>,
+}
+
+/// Represents an event about user
+///
+/// Read more at [Codewars documentation](https://dev.codewars.com/#webhooks)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UserEvent {
+    /// User's rank was upgraded
+    RankUpgraded {
+        /// User with new rank
+        user: UserRankInfo,
+    },
+    /// User earned rank in a new language
+    RankEarned {
+        /// User with earned rank
+        user: UserRankInfo,
+    },
+    /// User's honor was changed
+    HonorChanged {
+        /// User with new honor
+        user: UserHonorInfo,
+    },
+}
+
+/// Represents a user in rank events
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct UserRankInfo {
+    /// ID of the user
+    pub id: String,
+    /// New rank. [-8, -1] maps to kyu, [1, 8] maps to dan
+    pub rank: i8,
+    /// Language of the rank, if this is not an overall rank
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Represents a user in honor events
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Default, Clone)]
+pub struct UserHonorInfo {
+    /// ID of the user
+    pub id: String,
+    /// New honor of the user
+    pub honor: u64,
+    /// Honor of the user before the change
+    pub honor_was: u64,
+}
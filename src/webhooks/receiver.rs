@@ -0,0 +1,322 @@
+//! Receiver for Codewars webhooks
+//!
+//! Receiver doesn't depend on any HTTP server. Pass headers and body of incoming requests to
+//! [`WebhookReceiver::receive`] and run handlers with [`WebhookReceiver::process_pending`].
+
+use crate::webhooks::inbox::{Delivery, WebhookInbox};
+use crate::webhooks::models::WebhookEvent;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// Receiver for Codewars webhooks that stores deliveries in an inbox
+///
+/// Deliveries are marked as processed only after handler succeeded, so every delivery is
+/// handled at least once, even if the process is restarted between receiving and handling.
+#[derive(Debug)]
+pub struct WebhookReceiver<I: WebhookInbox> {
+    inbox: I,
+    secret: Option<String>,
+}
+
+/// Implementation of WebhookReceiver
+impl<I: WebhookInbox> WebhookReceiver<I> {
+    /// Create new receiver that accepts deliveries without checking secret
+    ///
+    /// # Arguments:
+    /// * inbox (I) - inbox to store deliveries in
+    pub fn new(inbox: I) -> Self {
+        Self {
+            inbox,
+            secret: None,
+        }
+    }
+
+    /// Create new receiver that accepts only deliveries with the given secret
+    ///
+    /// # Arguments:
+    /// * inbox (I) - inbox to store deliveries in
+    /// * secret (&str) - secret configured in Codewars account settings
+    pub fn with_secret(inbox: I, secret: &str) -> Self {
+        Self {
+            inbox,
+            secret: Some(secret.to_string()),
+        }
+    }
+
+    /// Get inbox of this receiver
+    pub fn inbox(&self) -> &I {
+        &self.inbox
+    }
+
+    /// Check secret, validate payload and store delivery in the inbox
+    ///
+    /// # Arguments:
+    /// * secret (Option<&str>) - value of `X-Webhook-Secret` header
+    /// * delivery (Delivery) - received delivery
+    ///
+    /// # Returns:
+    /// * Result<bool, String> - `false` if the delivery is a duplicate of already stored one
+    ///
+    /// # Errors:
+    /// * `invalid webhook secret` - If secret is configured and doesn't match
+    /// * `invalid webhook payload: {error}` - If the delivery can't be parsed, see [`WebhookEvent::parse`]
+    /// * Error of the inbox - If the delivery can't be stored
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::webhooks::inbox::{Delivery, MemoryInbox};
+    /// use codewars_api::webhooks::receiver::WebhookReceiver;
+    ///
+    /// let mut receiver = WebhookReceiver::with_secret(MemoryInbox::new(), "secret");
+    /// let body = r#"{"action":"honor_changed","user":{"id":"5a1b","honor":58,"honor_was":56}}"#;
+    /// assert!(receiver.receive(Some("secret"), Delivery::new("user", body)).unwrap());
+    /// // Retried delivery is ignored
+    /// assert!(!receiver.receive(Some("secret"), Delivery::new("user", body)).unwrap());
+    /// ```
+    pub fn receive(&mut self, secret: Option<&str>, delivery: Delivery) -> Result<bool, String> {
+        if let Some(expected) = &self.secret {
+            if !secret.is_some_and(|secret| secrets_match(secret, expected)) {
+                return Err("invalid webhook secret".to_string());
+            }
+        }
+        // Don't store deliveries which would fail processing on every attempt
+        delivery
+            .parse()
+            .map_err(|err| format!("invalid webhook payload: {}", err))?;
+        self.inbox.store(delivery)
+    }
+
+    /// Run handler for every pending delivery in order of receiving
+    ///
+    /// Processing stops at the first delivery that can't be handled, and this delivery stays
+    /// pending, so it will be retried on the next call. Deliveries that can't be parsed, e.g.
+    /// stored directly in the inbox, are marked as processed without running the handler, so they
+    /// don't block later deliveries.
+    ///
+    /// # Arguments:
+    /// * handler (FnMut(&WebhookEvent) -> Result<(), String>) - handler for events
+    ///
+    /// # Returns:
+    /// * Result<usize, String> - Result that contains number of handled deliveries or an error message
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::webhooks::inbox::{Delivery, MemoryInbox};
+    /// use codewars_api::webhooks::receiver::WebhookReceiver;
+    ///
+    /// let mut receiver = WebhookReceiver::new(MemoryInbox::new());
+    /// let body = r#"{"action":"rank_earned","user":{"id":"5a1b","rank":-8,"language":"rust"}}"#;
+    /// receiver.receive(None, Delivery::new("user", body)).unwrap();
+    /// let processed = receiver
+    ///     .process_pending(|event| {
+    ///         println!("{:?}", event);
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(processed, 1);
+    /// ```
+    pub fn process_pending<F>(&mut self, mut handler: F) -> Result<usize, String>
+    where
+        F: FnMut(&WebhookEvent) -> Result<(), String>,
+    {
+        let mut processed = 0;
+        for delivery in self.inbox.pending()? {
+            // Parsing would fail again on retry, so unparsable delivery is skipped
+            if let Ok(event) = delivery.parse() {
+                handler(&event)?;
+                processed += 1;
+            }
+            self.inbox.mark_processed(&delivery.id)?;
+        }
+        Ok(processed)
+    }
+
+    /// Run handler again for every delivery received in the time range, processed or not
+    ///
+    /// Deliveries that can't be parsed are skipped
+    ///
+    /// # Arguments:
+    /// * from (DateTime<Utc>) - start of the range, inclusive
+    /// * to (DateTime<Utc>) - end of the range, exclusive
+    /// * handler (FnMut(&WebhookEvent) -> Result<(), String>) - handler for events
+    ///
+    /// # Returns:
+    /// * Result<usize, String> - Result that contains number of replayed deliveries or an error message
+    pub fn replay<F>(
+        &mut self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        mut handler: F,
+    ) -> Result<usize, String>
+    where
+        F: FnMut(&WebhookEvent) -> Result<(), String>,
+    {
+        let pending: HashSet<String> = self
+            .inbox
+            .pending()?
+            .into_iter()
+            .map(|delivery| delivery.id)
+            .collect();
+        let mut replayed = 0;
+        for delivery in self.inbox.received_between(from, to)? {
+            let Ok(event) = delivery.parse() else {
+                continue;
+            };
+            handler(&event)?;
+            // Replayed delivery doesn't have to be processed again, processed ones are already marked
+            if pending.contains(&delivery.id) {
+                self.inbox.mark_processed(&delivery.id)?;
+            }
+            replayed += 1;
+        }
+        Ok(replayed)
+    }
+}
+
+// Compare secrets in constant time. Hashes are compared, so time doesn't depend on length either
+fn secrets_match(secret: &str, expected: &str) -> bool {
+    let secret = Sha256::digest(secret.as_bytes());
+    let expected = Sha256::digest(expected.as_bytes());
+    secret
+        .iter()
+        .zip(expected.iter())
+        .fold(0, |difference, (a, b)| difference | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for webhook receiver
+
+    use super::*;
+    use crate::webhooks::inbox::{FileInbox, MemoryInbox};
+    use crate::webhooks::models::UserEvent;
+    use chrono::Duration;
+
+    const HONOR_CHANGED: &str =
+        r#"{"action":"honor_changed","user":{"id":"5a1b","honor":58,"honor_was":56}}"#;
+    const RANK_EARNED: &str =
+        r#"{"action":"rank_earned","user":{"id":"5a1b","rank":-8,"language":"rust"}}"#;
+
+    /// Test that deliveries with wrong secret are rejected
+    #[test]
+    fn test_reject_invalid_secret() {
+        let mut receiver = WebhookReceiver::with_secret(MemoryInbox::new(), "secret");
+        let result = receiver.receive(Some("wrong"), Delivery::new("user", HONOR_CHANGED));
+        assert_eq!(result, Err("invalid webhook secret".to_string()));
+        assert!(receiver
+            .receive(None, Delivery::new("user", HONOR_CHANGED))
+            .is_err());
+        assert!(receiver.inbox().pending().unwrap().is_empty());
+        assert!(receiver
+            .receive(Some("secret"), Delivery::new("user", HONOR_CHANGED))
+            .unwrap());
+    }
+
+    /// Test that malformed deliveries don't block later ones
+    #[test]
+    fn test_malformed_delivery() {
+        let mut receiver = WebhookReceiver::new(MemoryInbox::new());
+        let result = receiver.receive(None, Delivery::new("user", "{"));
+        assert!(result.unwrap_err().starts_with("invalid webhook payload: "));
+        assert!(receiver.inbox().pending().unwrap().is_empty());
+
+        // Delivery stored bypassing validation, followed by a valid one
+        let mut inbox = MemoryInbox::new();
+        inbox.store(Delivery::new("user", "{")).unwrap();
+        inbox.store(Delivery::new("user", HONOR_CHANGED)).unwrap();
+        let mut receiver = WebhookReceiver::new(inbox);
+        let mut handled = Vec::new();
+        let processed = receiver
+            .process_pending(|event| {
+                handled.push(event.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(processed, 1);
+        assert_eq!(
+            handled,
+            vec![WebhookEvent::parse("user", HONOR_CHANGED).unwrap()]
+        );
+        assert!(receiver.inbox().pending().unwrap().is_empty());
+    }
+
+    /// Test that failed deliveries are handled again after restart
+    #[test]
+    fn test_at_least_once_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inbox.jsonl");
+        {
+            let mut receiver = WebhookReceiver::new(FileInbox::open(&path).unwrap());
+            receiver
+                .receive(None, Delivery::new("user", HONOR_CHANGED))
+                .unwrap();
+            receiver
+                .receive(None, Delivery::new("user", RANK_EARNED))
+                .unwrap();
+            let result = receiver.process_pending(|event| match event {
+                WebhookEvent::User(UserEvent::RankEarned { .. }) => Err("crash".to_string()),
+                _ => Ok(()),
+            });
+            assert_eq!(result, Err("crash".to_string()));
+        }
+        let mut receiver = WebhookReceiver::new(FileInbox::open(&path).unwrap());
+        let mut handled = Vec::new();
+        let processed = receiver
+            .process_pending(|event| {
+                handled.push(event.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(processed, 1);
+        assert_eq!(
+            handled,
+            vec![WebhookEvent::parse("user", RANK_EARNED).unwrap()]
+        );
+    }
+
+    /// Test replaying deliveries in the time range
+    #[test]
+    fn test_replay_time_range() {
+        let mut receiver = WebhookReceiver::new(MemoryInbox::new());
+        let now = Utc::now();
+        let mut old = Delivery::new("user", HONOR_CHANGED);
+        old.received_at = now - Duration::days(2);
+        let mut recent = Delivery::new("user", RANK_EARNED);
+        recent.received_at = now - Duration::hours(1);
+        receiver.receive(None, old).unwrap();
+        receiver.receive(None, recent).unwrap();
+        receiver.process_pending(|_| Ok(())).unwrap();
+        let mut replayed = 0;
+        let count = receiver
+            .replay(now - Duration::days(1), now, |_| {
+                replayed += 1;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!((count, replayed), (1, 1));
+    }
+
+    /// Test that replaying processed deliveries doesn't append processed marks again
+    #[test]
+    fn test_replay_keeps_file_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inbox.jsonl");
+        let mut receiver = WebhookReceiver::new(FileInbox::open(&path).unwrap());
+        let now = Utc::now();
+        receiver
+            .receive(None, Delivery::new("user", HONOR_CHANGED))
+            .unwrap();
+        receiver
+            .replay(now - Duration::days(1), now + Duration::days(1), |_| Ok(()))
+            .unwrap();
+        let length = std::fs::metadata(&path).unwrap().len();
+        let count = receiver
+            .replay(now - Duration::days(1), now + Duration::days(1), |_| Ok(()))
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), length);
+        assert!(receiver.inbox.pending().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,418 @@
+//! Simulator that sends sample webhook events to a local endpoint
+//!
+//! Events are built from a [`User`], so they look like events Codewars would send for this user.
+//! Sequences of events are described with scripts like `honor_changed then rank_upgraded`.
+
+use crate::rest_api::models::{CodeChallenges, Rank, Ranks, User};
+use crate::webhooks::models::{
+    CodeChallengeEvent, CodeChallengeInfo, UserEvent, UserHonorInfo, UserRankInfo, WebhookEvent,
+};
+use std::collections::HashMap;
+
+/// Represents a step of simulation script
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Step {
+    /// User's honor changed by the given amount
+    HonorChanged(i64),
+    /// User's rank was upgraded. Overall rank if language is not set
+    RankUpgraded(Option<String>),
+    /// User earned rank in a new language
+    RankEarned(String),
+    /// User created kata with the given ID
+    KataCreated(String),
+    /// User's kata with the given ID was approved
+    KataApproved(String),
+    /// Someone voted for user's kata with the given ID
+    KataVoted(String),
+}
+
+/// Implementation of Step
+impl Step {
+    /// Parse script into list of steps
+    ///
+    /// Steps are separated by `then`, `,` or new lines. Each step is an action name with
+    /// optional argument after colon:
+    /// * `honor_changed[:amount]` - amount defaults to 2
+    /// * `rank_upgraded[:language]` - upgrades overall rank if language is not set
+    /// * `rank_earned[:language]` - language defaults to `rust`
+    /// * `created[:kata_id]`, `approved[:kata_id]`, `voted[:kata_id]`
+    ///
+    /// # Errors:
+    /// * `unknown step: {step}` - If action name is unknown
+    /// * `invalid honor amount: {amount}` - If amount of honor is not a number
+    ///
+    /// # Examples
+    /// ```
+    /// use codewars_api::webhooks::simulator::Step;
+    ///
+    /// let steps = Step::parse_script("honor_changed:10 then rank_upgraded:python").unwrap();
+    /// assert_eq!(
+    ///     steps,
+    ///     vec![Step::HonorChanged(10), Step::RankUpgraded(Some("python".to_string()))]
+    /// );
+    /// ```
+    pub fn parse_script(script: &str) -> Result<Vec<Step>, String> {
+        script
+            .replace(',', "\n")
+            .lines()
+            .flat_map(|line| line.split(" then "))
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+            .map(Step::parse)
+            .collect()
+    }
+
+    // Parse a single step
+    fn parse(step: &str) -> Result<Step, String> {
+        let (action, argument) = match step.split_once(':') {
+            Some((action, argument)) => (action.trim(), Some(argument.trim().to_string())),
+            None => (step, None),
+        };
+        match action {
+            "honor_changed" => match argument {
+                Some(amount) => amount
+                    .trim_start_matches('+')
+                    .parse()
+                    .map(Step::HonorChanged)
+                    .map_err(|_| format!("invalid honor amount: {}", amount)),
+                None => Ok(Step::HonorChanged(2)),
+            },
+            "rank_upgraded" => Ok(Step::RankUpgraded(argument)),
+            "rank_earned" => Ok(Step::RankEarned(
+                argument.unwrap_or_else(|| "rust".to_string()),
+            )),
+            "created" => Ok(Step::KataCreated(argument.unwrap_or_else(sample_kata_id))),
+            "approved" => Ok(Step::KataApproved(argument.unwrap_or_else(sample_kata_id))),
+            "voted" => Ok(Step::KataVoted(argument.unwrap_or_else(sample_kata_id))),
+            _ => Err(format!("unknown step: {}", step)),
+        }
+    }
+}
+
+// ID of kata used when script doesn't specify it
+fn sample_kata_id() -> String {
+    "5277c8a221e209d3f6000b56".to_string()
+}
+
+/// Build user for simulation when real one is not available
+///
+/// User has 8 kyu overall rank and no language ranks
+pub fn sample_user(username: &str) -> User {
+    User {
+        username: username.to_string(),
+        name: username.to_string(),
+        honor: 0,
+        clan: String::new(),
+        leaderboard_position: 0,
+        skills: Vec::new(),
+        ranks: Ranks {
+            overall: Rank::from_value(-8, 0),
+            languages: HashMap::new(),
+        },
+        code_challenges: CodeChallenges::default(),
+    }
+}
+
+// Get rank after upgrade. There is no zero rank, so 1 kyu is followed by 1 dan.
+// Returns `None` for 8 dan, which is the highest rank
+fn next_rank(rank: i8) -> Option<i8> {
+    match rank {
+        -1 => Some(1),
+        8.. => None,
+        other => Some(other + 1),
+    }
+}
+
+/// Builds webhook events for a user and keeps track of user's state between them
+#[derive(Debug, Clone)]
+pub struct EventBuilder {
+    user: User,
+    user_id: String,
+}
+
+/// Implementation of EventBuilder
+impl EventBuilder {
+    /// Create new builder for the user
+    ///
+    /// User model doesn't contain ID, so username is used as ID of the user
+    ///
+    /// # Arguments:
+    /// * user (User) - user to build events for, e.g. returned by `get_user`
+    pub fn new(user: User) -> Self {
+        Self {
+            user_id: user.username.clone(),
+            user,
+        }
+    }
+
+    /// Get user with all changes from built events applied
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    /// Build event for the step and apply its changes to the user
+    ///
+    /// Returns `None` if the step can't happen, i.e. upgrading rank of 8 dan or earning rank in a
+    /// language which already has one
+    pub fn build(&mut self, step: &Step) -> Option<WebhookEvent> {
+        Some(match step {
+            Step::HonorChanged(amount) => {
+                let honor_was = self.user.honor;
+                self.user.honor = honor_was.saturating_add_signed(*amount);
+                WebhookEvent::User(UserEvent::HonorChanged {
+                    user: UserHonorInfo {
+                        id: self.user_id.clone(),
+                        honor: self.user.honor,
+                        honor_was,
+                    },
+                })
+            }
+            Step::RankUpgraded(language) => {
+                let rank = match language {
+                    Some(language) => self
+                        .user
+                        .ranks
+                        .languages
+                        .entry(language.clone())
+                        .or_insert_with(|| Rank::from_value(-8, 0)),
+                    None => &mut self.user.ranks.overall,
+                };
+                *rank = Rank::from_value(next_rank(rank.rank)?, rank.score);
+                WebhookEvent::User(UserEvent::RankUpgraded {
+                    user: UserRankInfo {
+                        id: self.user_id.clone(),
+                        rank: rank.rank,
+                        language: language.clone(),
+                    },
+                })
+            }
+            Step::RankEarned(language) => {
+                if self.user.ranks.languages.contains_key(language) {
+                    return None;
+                }
+                let rank = self
+                    .user
+                    .ranks
+                    .languages
+                    .entry(language.clone())
+                    .or_insert(Rank::from_value(-8, 0));
+                WebhookEvent::User(UserEvent::RankEarned {
+                    user: UserRankInfo {
+                        id: self.user_id.clone(),
+                        rank: rank.rank,
+                        language: Some(language.clone()),
+                    },
+                })
+            }
+            Step::KataCreated(id) => {
+                self.user.code_challenges.total_authored += 1;
+                WebhookEvent::CodeChallenge(CodeChallengeEvent::Created {
+                    code_challenge: self.code_challenge(id, None),
+                })
+            }
+            Step::KataApproved(id) => WebhookEvent::CodeChallenge(CodeChallengeEvent::Approved {
+                code_challenge: self.code_challenge(id, Some("codewars".to_string())),
+            }),
+            Step::KataVoted(id) => WebhookEvent::CodeChallenge(CodeChallengeEvent::Voted {
+                code_challenge: self.code_challenge(id, None),
+            }),
+        })
+    }
+
+    /// Build events for all steps in order, skipping steps which can't happen
+    pub fn build_all(&mut self, steps: &[Step]) -> Vec<WebhookEvent> {
+        steps.iter().filter_map(|step| self.build(step)).collect()
+    }
+
+    // Build code challenge authored by the user
+    fn code_challenge(&self, id: &str, approved_by_id: Option<String>) -> CodeChallengeInfo {
+        CodeChallengeInfo {
+            id: id.to_string(),
+            created_by_id: self.user_id.clone(),
+            approved_by_id,
+        }
+    }
+}
+
+/// Sends webhook events to an endpoint the same way Codewars does
+#[derive(Debug, Clone)]
+pub struct WebhookSimulator {
+    url: String,
+    secret: Option<String>,
+    client: reqwest::Client,
+}
+
+/// Implementation of WebhookSimulator
+impl WebhookSimulator {
+    /// Create new simulator that sends events to the URL without secret
+    ///
+    /// # Arguments:
+    /// * url (&str) - URL of the endpoint, e.g. `http://localhost:8080/webhooks`
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            secret: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create new simulator that sends events to the URL with secret in `X-Webhook-Secret` header
+    ///
+    /// # Arguments:
+    /// * url (&str) - URL of the endpoint
+    /// * secret (&str) - secret configured for the endpoint
+    pub fn with_secret(url: &str, secret: &str) -> Self {
+        Self {
+            secret: Some(secret.to_string()),
+            ..Self::new(url)
+        }
+    }
+
+    /// Send event to the endpoint
+    ///
+    /// # Errors:
+    /// * `unexpected status code: {status_code}` - If the endpoint responded with non-success status code
+    /// * `error sending request` - If the request can't be sent
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use codewars_api::webhooks::simulator::{sample_user, EventBuilder, Step, WebhookSimulator};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let simulator = WebhookSimulator::with_secret("http://localhost:8080/webhooks", "secret");
+    /// let mut builder = EventBuilder::new(sample_user("some_user"));
+    /// let event = builder.build(&Step::HonorChanged(10)).unwrap();
+    /// simulator.send(&event).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn send(&self, event: &WebhookEvent) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header("X-Webhook-Event", event.event_name())
+            .body(event.body());
+        if let Some(secret) = &self.secret {
+            request = request.header("X-Webhook-Secret", secret);
+        }
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        // Check status code
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            other => Err(format!("unexpected status code: {}", other)),
+        }
+    }
+
+    /// Send events one by one, stopping at the first error
+    ///
+    /// # Returns:
+    /// * Result<usize, String> - Result that contains number of sent events or an error message
+    pub async fn send_all(&self, events: &[WebhookEvent]) -> Result<usize, String> {
+        for event in events {
+            self.send(event).await?;
+        }
+        Ok(events.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for webhook simulator
+
+    use super::*;
+    use crate::fixtures;
+    use crate::rest_api::models::Color;
+
+    /// Test building events from a script on a real user
+    #[test]
+    fn test_build_events_from_script() {
+        let mut builder = EventBuilder::new(fixtures::user());
+        let steps =
+            Step::parse_script("honor_changed:5 then rank_upgraded, rank_earned:go").unwrap();
+        let events = builder.build_all(&steps);
+        assert_eq!(
+            events,
+            vec![
+                WebhookEvent::User(UserEvent::HonorChanged {
+                    user: UserHonorInfo {
+                        id: "some_user".to_string(),
+                        honor: 549,
+                        honor_was: 544,
+                    }
+                }),
+                WebhookEvent::User(UserEvent::RankUpgraded {
+                    user: UserRankInfo {
+                        id: "some_user".to_string(),
+                        rank: -2,
+                        language: None,
+                    }
+                }),
+                WebhookEvent::User(UserEvent::RankEarned {
+                    user: UserRankInfo {
+                        id: "some_user".to_string(),
+                        rank: -8,
+                        language: Some("go".to_string()),
+                    }
+                }),
+            ]
+        );
+        assert_eq!(builder.user().ranks.overall.color, Color::Purple);
+    }
+
+    /// Test that rank isn't upgraded above 8 dan
+    #[test]
+    fn test_no_upgrade_above_8_dan() {
+        let mut user = sample_user("some_user");
+        user.ranks.overall = Rank::from_value(7, 0);
+        let mut builder = EventBuilder::new(user);
+        let events = builder.build_all(&[Step::RankUpgraded(None), Step::RankUpgraded(None)]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(builder.user().ranks.overall.rank, 8);
+        assert_eq!(builder.build(&Step::RankUpgraded(None)), None);
+    }
+
+    /// Test that rank isn't earned again in a language which already has one
+    #[test]
+    fn test_no_rank_earned_twice() {
+        let mut builder = EventBuilder::new(fixtures::user());
+        let ruby = builder.user().ranks.languages["ruby"].clone();
+        assert_eq!(builder.build(&Step::RankEarned("ruby".to_string())), None);
+        assert_eq!(builder.user().ranks.languages["ruby"], ruby);
+        let events = builder.build_all(&[
+            Step::RankEarned("go".to_string()),
+            Step::RankEarned("go".to_string()),
+        ]);
+        assert_eq!(events.len(), 1);
+    }
+
+    /// Test that unknown steps are rejected
+    #[test]
+    fn test_parse_unknown_step() {
+        assert_eq!(
+            Step::parse_script("honor_changed then rank_lost"),
+            Err("unknown step: rank_lost".to_string())
+        );
+    }
+
+    /// Test sending events with headers Codewars uses
+    #[tokio::test]
+    async fn test_send_events() {
+        let mut server = mockito::Server::new_async().await;
+        let simulator =
+            WebhookSimulator::with_secret(&format!("{}/webhooks", server.url()), "secret");
+        let mut builder = EventBuilder::new(sample_user("some_user"));
+        let event = builder
+            .build(&Step::RankEarned("rust".to_string()))
+            .unwrap();
+        let mock = server
+            .mock("POST", "/webhooks")
+            .match_header("x-webhook-event", "user")
+            .match_header("x-webhook-secret", "secret")
+            .match_body(mockito::Matcher::JsonString(event.body()))
+            .with_status(200)
+            .create_async()
+            .await;
+        simulator.send_all(&[event]).await.unwrap();
+        mock.assert_async().await;
+    }
+}